@@ -3,24 +3,43 @@ use std::{
     rc::Rc };
 use crate::{
     {Name, Unit, Value, Values}, 
+    presentation::{Colour, DiePresentation, DieShape, FacePresentation},
     random::{choose_from, Rng, default_rng}};
 
 
 /// `Die`/Dice are the most obvious inclusion in a dice-rolling program. 
 /// They're quite simple, with a name and a `Vec` of reference-counted `Face`s.
 /// The more complicated part is the collection of values in `Face`s. 
+///
+/// For UIs, a `Die` can also carry a `DiePresentation`, saying what colour and shape it is.
 #[derive(Clone, Debug)]
 pub struct Die {
     pub name: Name,
     pub faces: Vec<Rc<Face>>,
-    pub explode_on: Option<Rc<dyn Unit>>
+    pub explode_on: Option<Rc<dyn Unit>>,
+    pub presentation: DiePresentation,
 }
 impl  Die {
     pub fn new(name: Name, faces: Vec<Rc<Face>>) -> Rc<Self> {
         Rc::new(Self { 
             name, 
             faces, 
-            explode_on: None}) }
+            explode_on: None,
+            presentation: DiePresentation::default() }) }
+
+    /// Sets the colour it should be drawn in
+    pub fn coloured(self: Rc<Self>, colour: Colour) -> Rc<Self> {
+        let presentation = DiePresentation { colour: Some(colour), ..self.presentation.clone() };
+        Rc::new(Self { presentation, ..Rc::unwrap_or_clone(self) }) }
+
+    /// Sets the shape it should be drawn as, for when it isn't the usual one for its number of faces
+    pub fn shaped(self: Rc<Self>, shape: DieShape) -> Rc<Self> {
+        let presentation = DiePresentation { shape: Some(shape), ..self.presentation.clone() };
+        Rc::new(Self { presentation, ..Rc::unwrap_or_clone(self) }) }
+
+    /// The shape it was given, or else the usual one for its number of faces
+    pub fn shape(&self) -> DieShape {
+        self.presentation.shape.unwrap_or_else(|| DieShape::for_faces(self.faces.len())) }
     
     
     pub fn exploding_on(mut self, explode_on: Rc<dyn Unit>) -> Self {
@@ -49,15 +68,16 @@ fn name(name: &str) -> Name { Name::new(name).unwrap() }
 
 
 /// A `Face` of a `Die`. Has a label in order to have a short bit of text for
-/// display purposes.
+/// display purposes. UIs that want more than that can look at its `FacePresentation`.
 #[derive(Clone, Debug)]
 pub struct Face {
     pub label: Name,
     pub values: Values,
+    pub presentation: FacePresentation,
 }
 impl Face {
     pub fn new(label: Name, values: Vec<Value>) -> Rc<Self> {
-        Rc::new(Face{ label, values: Values::from(values) }) }
+        Rc::new(Face{ label, values: Values::from(values), presentation: FacePresentation::default() }) }
     
     pub fn with_one_val(label: Name, value: Value) -> Rc<Self> {
         Self::new(label, vec![value]) }
@@ -70,8 +90,114 @@ impl Face {
     
     pub fn value_for(&self, unit: &Rc<dyn Unit>) -> Option<i32> {
         self.values.value_for(unit) }
+
+    /// Sets the text to draw on the face
+    pub fn with_glyph(self: Rc<Self>, glyph: &str) -> Rc<Self> {
+        let presentation = FacePresentation { glyph: Some(glyph.to_string()), ..self.presentation.clone() };
+        Rc::new(Self { presentation, ..Rc::unwrap_or_clone(self) }) }
+
+    /// Sets the key a UI can use to look up its own image for the face
+    pub fn with_icon(self: Rc<Self>, icon: &str) -> Rc<Self> {
+        let presentation = FacePresentation { icon: Some(icon.to_string()), ..self.presentation.clone() };
+        Rc::new(Self { presentation, ..Rc::unwrap_or_clone(self) }) }
+
+    /// Sets labels for tight spaces and for when there's room to spell everything out
+    pub fn with_labels(self: Rc<Self>, short_label: &str, long_label: &str) -> Rc<Self> {
+        let presentation = FacePresentation {
+            short_label: Some(short_label.to_string()),
+            long_label: Some(long_label.to_string()),
+            ..self.presentation.clone() };
+        Rc::new(Self { presentation, ..Rc::unwrap_or_clone(self) }) }
+
+    /// The short label it was given, or else its label
+    pub fn short_label(&self) -> &str {
+        self.presentation.short_label.as_deref().unwrap_or(&self.label) }
+
+    /// The long label it was given, or else its label
+    pub fn long_label(&self) -> &str {
+        self.presentation.long_label.as_deref().unwrap_or(&self.label) }
 }
 impl Display for Face {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         f.write_str(&self.label) }
 }
+
+
+/// Declares a whole set of dice at once: the `Unit`s it uses, the `Face`s built from those units,
+/// and the `Die`s built from those faces. It evaluates to a `(Vec<Rc<dyn Unit>>, Vec<Rc<Die>>)`,
+/// each in the order they were declared.
+///
+/// ```text
+/// dice_set! {
+///     units {
+///         success = TieredUnit::pos_zero_neg(name!("Success"), "{} Successes", "{} Successes", "{|} Failures"),
+///         advantage = TieredUnit::pos_neg(name!("Advantage"), "{} Advantage", "{|} Threat"),
+///     }
+///     faces {
+///         succ = "Success" as "success" { success: 1 },
+///         succ_adv = "Success + Advantage" as "success_advantage" { success: 1, advantage: 1 },
+///         blank = "_" { success: 0 },
+///     }
+///     dice {
+///         "Boost": [succ, succ_adv, blank * 2],
+///     }
+/// }
+/// ```
+///
+/// Faces and units are referred to by the identifiers they're given, so misspelling one is a
+/// compile error, as is a face or die label that isn't a valid `Name`. A face that's declared but
+/// never put on a die shows up as an unused variable warning. Follow a face's label with `as "key"`
+/// to give it an icon key (see `Face::with_icon()`), and follow a face with `* n` to put it on the
+/// die `n` times.
+#[macro_export]
+macro_rules! dice_set {
+    (@count) => { 1 };
+    (@count $count:literal) => { $count };
+    (
+        units { $($unit:ident = $unit_expr:expr),* $(,)? }
+        faces { $($face:ident = $label:literal $(as $icon:literal)? { $($face_unit:ident : $amount:expr),* $(,)? }),* $(,)? }
+        dice { $($die_name:literal : [ $($die_face:ident $(* $count:literal)?),* $(,)? ]),* $(,)? }
+    ) => {{
+        $( let $unit: ::std::rc::Rc<dyn $crate::Unit> = $unit_expr; )*
+        $( let $face = $crate::dice::Face::new(
+            $crate::name!($label),
+            vec![$( $crate::Value { unit: $face_unit.clone(), value: $amount } ),*]);
+            $( let $face = $face.with_icon($icon); )? )*
+        let dice: Vec<::std::rc::Rc<$crate::dice::Die>> = vec![$(
+            $crate::dice::Die::new(
+                $crate::name!($die_name),
+                {
+                    let mut faces = Vec::new();
+                    $( for _ in 0..$crate::dice_set!(@count $($count)?) {
+                        faces.push($die_face.clone()); } )*
+                    faces
+                }) ),*];
+        (vec![$($unit),*], dice) }};
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{name, units::BasicUnit};
+
+    #[test]
+    fn dice_set_repeats_faces() {
+        let (units, dice) = dice_set! {
+            units {
+                pips = BasicUnit::new(name!("Pips"), "{} Pips", false),
+            }
+            faces {
+                one = "1" as "one" { pips: 1 },
+                two = "2" { pips: 2 },
+            }
+            dice {
+                "Lopsided": [one * 3, two],
+            }
+        };
+        assert_eq!(units.len(), 1);
+        let labels: Vec<String> = dice[0].faces.iter().map(|face| face.to_string()).collect();
+        assert_eq!(labels, vec!["1", "1", "1", "2"]);
+        assert_eq!(dice[0].faces[3].value_for(&units[0]), Some(2));
+        assert_eq!(dice[0].faces[0].presentation.icon.as_deref(), Some("one"));
+        assert_eq!(dice[0].faces[3].presentation.icon, None); }
+}
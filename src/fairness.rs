@@ -0,0 +1,349 @@
+//! Goodness-of-fit tests for checking that dice and `Rng`s actually behave the way they're
+//! supposed to. These are meant to be used in test suites, especially for custom `Die`s, rollers,
+//! and random number generators: roll the thing a bunch of times, then check how likely it is that
+//! a fair version would have produced results that far off from what's expected.
+//!
+//! There are two tests available. The chi-square test compares the counts of each possible outcome
+//! to how many of each were expected, and it's the one to use for faces and indices, which don't
+//! have a natural order. The Kolmogorov–Smirnov test compares the cumulative distribution of the
+//! totals to the expected one, so it's better at noticing when totals are skewed high or low.
+//!
+//! Both give back a `FitResult` with a p-value: the probability that a fair roller would have come
+//! out at least this far from the expectation. A tiny p-value (such as below 0.001) means it almost
+//! certainly isn't fair. Keep in mind that even a fair roller will fail now and then at whatever
+//! significance level you pick, so use a fixed seed in tests to keep them from being flaky.
+
+use std::{
+    fmt::{Display, Formatter},
+    num::NonZero,
+    rc::Rc };
+use crate::{
+    Unit,
+    dice::{Die, Face},
+    random::Rng,
+    rollers::SubRoller };
+
+
+/// Which goodness-of-fit test produced a `FitResult`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FitTest {
+    ChiSquare, KolmogorovSmirnov
+}
+impl Display for FitTest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FitTest::ChiSquare => f.write_str("Chi-square"),
+            FitTest::KolmogorovSmirnov => f.write_str("Kolmogorov-Smirnov") } }
+}
+
+
+/// The outcome of a goodness-of-fit test
+#[derive(Clone, Debug)]
+pub struct FitResult {
+    pub test: FitTest,
+    /// The test statistic; chi-square's sum of squared differences or Kolmogorov-Smirnov's largest
+    /// difference between the cumulative distributions
+    pub statistic: f64,
+    /// The probability that a fair roller would produce a statistic at least this large
+    pub p_value: f64,
+}
+impl FitResult {
+    /// `true` if the results are believable at the given significance level (i.e. 0.01), meaning
+    /// the p-value is at least that large.
+    pub fn passes(&self, significance: f64) -> bool { self.p_value >= significance }
+}
+impl Display for FitResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}: {:.4} (p = {:.4})", self.test, self.statistic, self.p_value)) }
+}
+
+
+/// The chance of each total coming up, used as the expectation for `roller_totals()`. Totals are
+/// kept in order and without repeats.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution {
+    probabilities: Vec<(i32, f64)>
+}
+impl Distribution {
+    /// Creates a `Distribution` from relative weights of each total, such as counts of how many
+    /// faces have that total. Repeated totals are combined, and the weights are scaled so that
+    /// they add up to 1.
+    pub fn from_weights(weights: impl IntoIterator<Item=(i32, f64)>) -> Self {
+        let mut probabilities: Vec<(i32, f64)> = Vec::new();
+        for (total, weight) in weights {
+            match probabilities.iter_mut().find(|(existing, _)| *existing == total) {
+                Some((_, existing_weight)) => *existing_weight += weight,
+                None => probabilities.push((total, weight)) } }
+        let sum: f64 = probabilities.iter().map(|(_, weight)| weight).sum();
+        for (_, weight) in probabilities.iter_mut() {
+            *weight /= sum; }
+        probabilities.sort_by_key(|(total, _)| *total);
+        Self { probabilities } }
+
+    /// The distribution of a single roll of the `Die` for the given `Unit`, where faces without a
+    /// `Value` for it count as the unit's `default_value()`. Explosions aren't taken into account.
+    pub fn for_die(die: &Die, unit: &Rc<dyn Unit>) -> Self {
+        Self::from_weights(die.faces.iter()
+            .map(|face| (face.values.total_for(unit), 1.0))) }
+
+    /// The distribution of adding a roll from this distribution to a roll from the other one
+    pub fn plus(&self, other: &Distribution) -> Self {
+        Self::from_weights(self.probabilities.iter()
+            .flat_map(|(total1, prob1)| other.probabilities.iter()
+                .map(move |(total2, prob2)| (total1 + total2, prob1 * prob2)))) }
+
+    /// The distribution of adding up `count` rolls from this distribution. A count of 0 always
+    /// totals 0.
+    pub fn times(&self, count: u32) -> Self {
+        (0..count).fold(
+            Self { probabilities: vec![(0, 1.0)] },
+            |sum, _| sum.plus(self)) }
+
+    /// The chance of the given total coming up
+    pub fn probability_of(&self, total: i32) -> f64 {
+        self.probabilities.iter()
+            .find(|(existing, _)| *existing == total)
+            .map_or(0.0, |(_, prob)| *prob) }
+
+    /// Iterates through each possible total and its probability, from lowest to highest total
+    pub fn iter(&self) -> impl Iterator<Item=&(i32, f64)> { self.probabilities.iter() }
+}
+
+
+/// Runs a chi-square test of the observed counts against the expected probabilities of each
+/// category. Both slices have to be the same length. The usual rule of thumb is that every
+/// category should expect at least 5 results; below that, the p-value isn't very accurate.
+pub fn chi_square(observed: &[u64], expected: &[f64]) -> FitResult {
+    assert_eq!(observed.len(), expected.len(), "observed and expected need the same number of categories");
+    let count = observed.iter().sum::<u64>() as f64;
+    let mut statistic = 0.0;
+    let mut categories = 0;
+    for (&obs, &prob) in observed.iter().zip(expected) {
+        if prob <= 0.0 {
+            // anything at all showing up where nothing should is as unfair as it gets
+            if obs > 0 { statistic = f64::INFINITY; }
+            continue; }
+        let exp = prob * count;
+        statistic += (obs as f64 - exp).powi(2) / exp;
+        categories += 1; }
+    let degrees_of_freedom = categories.max(2) - 1;
+    FitResult {
+        test: FitTest::ChiSquare,
+        statistic,
+        p_value: chi_square_p_value(statistic, degrees_of_freedom as f64) } }
+
+
+/// Runs a Kolmogorov-Smirnov test of the observed totals against the expected `Distribution`. For
+/// distributions with only a few possible totals (like most dice), this is on the cautious side,
+/// so the p-values come out larger than they really should be.
+pub fn kolmogorov_smirnov(observed: &[i32], expected: &Distribution) -> FitResult {
+    let mut sorted = observed.to_vec();
+    sorted.sort();
+    let count = sorted.len() as f64;
+
+    let mut totals: Vec<i32> = expected.iter().map(|(total, _)| *total).collect();
+    totals.extend(sorted.iter());
+    totals.sort();
+    totals.dedup();
+
+    let mut statistic: f64 = 0.0;
+    let mut expected_cdf = 0.0;
+    let mut num_observed = 0;
+    for total in totals {
+        expected_cdf += expected.probability_of(total);
+        num_observed += sorted[num_observed..].iter().take_while(|&&obs| obs == total).count();
+        statistic = statistic.max((num_observed as f64 / count - expected_cdf).abs()); }
+
+    let root_n = count.sqrt();
+    FitResult {
+        test: FitTest::KolmogorovSmirnov,
+        statistic,
+        p_value: kolmogorov_p_value((root_n + 0.12 + 0.11 / root_n) * statistic) } }
+
+
+/// Rolls the `Die` `samples` times and runs a chi-square test of how often each face came up. Faces
+/// that appear on the die more than once (the same `Rc<Face>`) are expected to come up that many
+/// times as often.
+pub fn die_faces(die: &Die, samples: NonZero<u32>, rng: Rng) -> FitResult {
+    let mut faces: Vec<&Rc<Face>> = Vec::new();
+    let mut weights: Vec<f64> = Vec::new();
+    for face in die.faces.iter() {
+        match faces.iter().position(|existing| Rc::ptr_eq(existing, face)) {
+            Some(idx) => weights[idx] += 1.0,
+            None => {
+                faces.push(face);
+                weights.push(1.0); } } }
+    let expected: Vec<f64> = weights.iter().map(|weight| weight / die.faces.len() as f64).collect();
+
+    let mut observed = vec![0; faces.len()];
+    for _ in 0..samples.get() {
+        let rolled = die.roll_face_with(rng.clone());
+        if let Some(idx) = faces.iter().position(|face| Rc::ptr_eq(face, &rolled)) {
+            observed[idx] += 1; } }
+    chi_square(&observed, &expected) }
+
+
+/// Has the `Rng` come up with `samples` indices for a collection of the given length and runs a
+/// chi-square test to see whether every index is equally likely.
+pub fn rng_indices(mut rng: Rng, length: usize, samples: NonZero<u32>) -> FitResult {
+    let mut observed = vec![0; length];
+    for _ in 0..samples.get() {
+        observed[rng.next_index(length)] += 1; }
+    chi_square(&observed, &vec![1.0 / length as f64; length]) }
+
+
+/// Rolls the roller `samples` times and runs both tests on its totals for the given `Unit` against
+/// the expected `Distribution`, returning the chi-square result, then the Kolmogorov-Smirnov one.
+/// Rolls without a `Value` for the unit count as its `default_value()`.
+pub fn roller_totals(
+    roller: Rc<dyn SubRoller>,
+    unit: &Rc<dyn Unit>,
+    expected: &Distribution,
+    samples: NonZero<u32>,
+    rng: Rng
+) -> (FitResult, FitResult) {
+    let totals: Vec<i32> = (0..samples.get())
+        .map(|_| roller.clone().inner_roll_with(rng.clone()).totals().total_for(unit))
+        .collect();
+
+    let mut categories: Vec<i32> = expected.iter().map(|(total, _)| *total).collect();
+    categories.extend(totals.iter().filter(|total| expected.probability_of(**total) == 0.0));
+    categories.sort();
+    categories.dedup();
+    let observed: Vec<u64> = categories.iter()
+        .map(|category| totals.iter().filter(|total| *total == category).count() as u64)
+        .collect();
+    let expected_probs: Vec<f64> = categories.iter()
+        .map(|category| expected.probability_of(*category))
+        .collect();
+
+    (chi_square(&observed, &expected_probs), kolmogorov_smirnov(&totals, expected)) }
+
+
+fn chi_square_p_value(statistic: f64, degrees_of_freedom: f64) -> f64 {
+    if statistic.is_infinite() { 0.0 }
+    else { upper_incomplete_gamma(degrees_of_freedom / 2.0, statistic / 2.0) } }
+
+
+/// Asymptotic Kolmogorov distribution, Q(λ) = 2 Σ (-1)^(k-1) e^(-2k²λ²)
+fn kolmogorov_p_value(lambda: f64) -> f64 {
+    if lambda < 0.2 { return 1.0 }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for k in 1..=100 {
+        let term = sign * (-2.0 * (k as f64).powi(2) * lambda.powi(2)).exp();
+        sum += term;
+        if term.abs() < 1e-12 { break; }
+        sign = -sign; }
+    (2.0 * sum).clamp(0.0, 1.0) }
+
+
+/// The regularized upper incomplete gamma function, Q(a, x), using the series for small x and the
+/// continued fraction otherwise
+fn upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 { return 1.0 }
+    let log_prefix = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..1000 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 { break; } }
+        (1.0 - sum * log_prefix.exp()).clamp(0.0, 1.0) }
+    else {
+        // Lentz's method
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny { d = tiny; }
+            c = b + an / c;
+            if c.abs() < tiny { c = tiny; }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-15 { break; } }
+        (log_prefix.exp() * h).clamp(0.0, 1.0) } }
+
+
+/// Lanczos approximation of ln(Γ(x)) for x > 0
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7];
+    if x < 0.5 {
+        // reflection formula
+        (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1.0 - x) }
+    else {
+        let x = x - 1.0;
+        let t = x + 7.5;
+        let series = COEFFICIENTS[1..].iter()
+            .enumerate()
+            .fold(COEFFICIENTS[0], |sum, (i, coef)| sum + coef / (x + i as f64 + 1.0));
+        0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln() } }
+
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZero, rc::Rc};
+    use crate::{
+        Unit,
+        fairness::{chi_square, die_faces, roller_totals, rng_indices, Distribution},
+        premade::number,
+        random::with_seed,
+        rollers::SubRoller };
+
+    fn samples(num: u32) -> NonZero<u32> { NonZero::new(num).unwrap() }
+
+    #[test]
+    fn chi_square_known_values() {
+        // 1 degree of freedom, statistic of 3.841 is the textbook 5% cutoff
+        let result = chi_square(&[60, 40], &[0.5, 0.5]);
+        assert!((result.statistic - 4.0).abs() < 1e-9);
+        assert!((result.p_value - 0.0455).abs() < 1e-3);
+        assert!(chi_square(&[50, 50], &[0.5, 0.5]).p_value > 0.999); }
+
+    #[test]
+    fn default_rng_is_fair() {
+        assert!(rng_indices(with_seed(12_345), 20, samples(20_000)).passes(0.001)); }
+
+    #[test]
+    fn d6_faces_are_fair() {
+        let (_, dice) = number::build();
+        assert!(die_faces(&dice[3], samples(6_000), with_seed(54_321)).passes(0.001)); }
+
+    #[test]
+    fn loaded_die_is_caught() {
+        let (unit, dice) = number::build();
+        let d6 = dice[3].clone();
+        let d2 = dice[0].clone();
+        let expected = Distribution::for_die(&d6, &unit);
+        let roller: Rc<dyn SubRoller> = d2;
+        let (chi, ks) = roller_totals(roller, &unit, &expected, samples(600), with_seed(7));
+        assert!(!chi.passes(0.001));
+        assert!(!ks.passes(0.001)); }
+
+    #[test]
+    fn pool_totals_match_convolution() {
+        let (unit, dice) = number::build();
+        let d6 = dice[3].clone();
+        let expected = Distribution::for_die(&d6, &unit).times(3);
+        assert!((expected.probability_of(3) - 1.0 / 216.0).abs() < 1e-12);
+        let unit: Rc<dyn Unit> = unit;
+        let (chi, ks) = roller_totals(d6.n_times(3), &unit, &expected, samples(20_000), with_seed(99));
+        assert!(chi.passes(0.001), "{}", chi);
+        assert!(ks.passes(0.001), "{}", ks); }
+}
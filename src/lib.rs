@@ -1,54 +1,60 @@
-#![feature(unsigned_is_multiple_of)]
-
-/// The UDice system is a dice-building and rolling library. The U stands for
-/// Universal, since it's meant to support any kind of dice system that's ever
-/// been invented (in theory). It can even represent a random table right in a
-/// single die (though some would likely be better with multiple dice, if the
-/// original table was built around multiple dice).
-///
-/// First, you have the `Die`, which has a name and some `Face`s. Each face has
-/// a name and some `Value`s. None of this should be surprising, other than maybe
-/// a `Face` having multiple `Value`s. There are plenty of dice systems out there
-/// that have multiple symbols with different meanings. 
-///
-/// How do these `Value`s work, though? They have 2 fields: a `Relationship` and
-/// an amount. The `Relationship` is pretty much the same idea as the 
-/// aforementioned symbol, except that in some systems, different symbols can
-/// work on the same `Relationship` (such as the concept of bane and boon symbols;
-/// They're different symbols, but they're positive and negative versions of the
-/// same `Relationship`. If you roll one of each, they cancel out). 
-///
-/// That's the primary part of the system, but the next part is how you get all
-/// to actually give you random results. That's where `Roller`s and `Roll`s come
-/// in. A `Roller` is what collects dice and modifiers together to form the hand
-/// of dice. Then you tell it to `roll()`, and it produces a `Roll`, which is the
-/// collection of all the `Face`s and `Value`s "rolled". It can describe all the
-/// individual rolls of all the individual dice, along with their modifiers, plus 
-/// it can give the final totals and results of the roll.
-///
-/// At this point in time, there are some decisions made to simplify the system, 
-/// largely because I don't know of any dice systems out there that don't work
-/// with the simple version, but I have thought of ways to make the system more
-/// universal if needed. The simplifications are that `Relationship`s always use
-/// an integer number for the amount, and that the only math used on those numbers
-/// is adding and subtracting (using the addition of negative numbers).
-/// This may change in the future, but I doubt it. The addition of other mathematical
-/// operations is  far more likely than needing something other than integers, but
-/// we'll see.
+//! The UDice system is a dice-building and rolling library. The U stands for
+//! Universal, since it's meant to support any kind of dice system that's ever
+//! been invented (in theory). It can even represent a random table right in a
+//! single die (though some would likely be better with multiple dice, if the
+//! original table was built around multiple dice).
+//!
+//! First, you have the `Die`, which has a name and some `Face`s. Each face has
+//! a name and some `Value`s. None of this should be surprising, other than maybe
+//! a `Face` having multiple `Value`s. There are plenty of dice systems out there
+//! that have multiple symbols with different meanings. 
+//!
+//! How do these `Value`s work, though? They have 2 fields: a `Relationship` and
+//! an amount. The `Relationship` is pretty much the same idea as the 
+//! aforementioned symbol, except that in some systems, different symbols can
+//! work on the same `Relationship` (such as the concept of bane and boon symbols;
+//! They're different symbols, but they're positive and negative versions of the
+//! same `Relationship`. If you roll one of each, they cancel out). 
+//!
+//! That's the primary part of the system, but the next part is how you get all
+//! to actually give you random results. That's where `Roller`s and `Roll`s come
+//! in. A `Roller` is what collects dice and modifiers together to form the hand
+//! of dice. Then you tell it to `roll()`, and it produces a `Roll`, which is the
+//! collection of all the `Face`s and `Value`s "rolled". It can describe all the
+//! individual rolls of all the individual dice, along with their modifiers, plus 
+//! it can give the final totals and results of the roll.
+//!
+//! At this point in time, there are some decisions made to simplify the system, 
+//! largely because I don't know of any dice systems out there that don't work
+//! with the simple version, but I have thought of ways to make the system more
+//! universal if needed. The simplifications are that `Relationship`s always use
+//! an integer number for the amount, and that the only math used on those numbers
+//! is adding and subtracting (using the addition of negative numbers).
+//! This may change in the future, but I doubt it. The addition of other mathematical
+//! operations is  far more likely than needing something other than integers, but
+//! we'll see.
 
 use std::{
-    error::Error
+    cmp::Ordering,
+    error::Error,
     fmt::{Display, Debug, Formatter},
+    hash::{Hash, Hasher},
     ops::{Deref, Neg},
     rc::Rc };
-use crate::rollers::{SubRoller, ModifierRoller};
+use crate::{
+    rollers::{SubRoller, ModifierRoller},
+    units::Tier };
 
 pub mod dice;
+pub mod fairness;
 pub mod premade;
+pub mod presentation;
 pub mod units;
 pub mod rollers;
 pub mod random;
+mod serialize;
 pub mod storage;
+pub mod svg;
 
 #[macro_export]
 macro_rules! clone_vec {
@@ -57,12 +63,18 @@ macro_rules! clone_vec {
     ($($items:expr),+) => {
         vec![$($items.clone()),+] }; }
 
+/// Creates a `Name` from a string literal, checking at compile time that it isn't empty and isn't
+/// too long, so the `unwrap()` inside can't fail.
+#[macro_export]
+macro_rules! name {
+    ($val:literal) => {{
+        const { assert!($crate::is_valid_name_literal($val), concat!("invalid name: \"", $val, "\"")) };
+        $crate::Name::new($val).unwrap() }}; }
+
 
 const MAX_NAME_LEN: usize = 35;
 
-//TODO: derive from Error
 /// Error possibilities for illegal names
-#[derive(Debug)]
 pub enum NameError {
     /// If the name is literally empty or is all whitespace, that's an Empty error
     Empty,
@@ -75,7 +87,7 @@ impl Display for NameError {
             NameError::Empty => 
                 f.write_str("name was empty or just whitespace"),
             NameError::TooLong => 
-                f.write_fmt(format_args!("name was longer than the max length of {}", MAX_NAME_LEN)) } }
+                f.write_fmt(format_args!("name was longer than the max length of {} characters", MAX_NAME_LEN)) } }
 }
 impl Debug for NameError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -88,9 +100,45 @@ impl Error for NameError {
 }
 
 
+/// Compile-time version of the checks in `Name::new()`, used by the `name!` macro. It counts every
+/// `char`, whitespace included, instead of characters as they're displayed after the whitespace is
+/// cleaned up, which is never fewer, so anything that passes will pass `Name::new()` too.
+#[doc(hidden)]
+pub const fn is_valid_name_literal(val: &str) -> bool {
+    let bytes = val.as_bytes();
+    let mut num_chars = 0;
+    let mut has_content = false;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        // decode the char by hand, since `chars()` can't be used in a const fn
+        let (len, first_bits) = match bytes[idx] {
+            0x00..=0x7F => (1, bytes[idx] as u32),
+            0xC0..=0xDF => (2, (bytes[idx] & 0x1F) as u32),
+            0xE0..=0xEF => (3, (bytes[idx] & 0x0F) as u32),
+            _ => (4, (bytes[idx] & 0x07) as u32) };
+        let mut code = first_bits;
+        let mut offset = 1;
+        while offset < len {
+            code = (code << 6) | (bytes[idx + offset] & 0x3F) as u32;
+            offset += 1; }
+        if let Some(c) = char::from_u32(code) && !c.is_whitespace() {
+            has_content = true; }
+        num_chars += 1;
+        idx += len; }
+    has_content && num_chars <= MAX_NAME_LEN }
+
+
 /// `Name` is a `String` wrapper for making valid names of things. It makes sure the string isn't
-/// empty and isn't too short. 
-#[derive(Clone, Debug)]
+/// empty and isn't too long. 
+///
+/// Whitespace is cleaned up, being trimmed off the ends and collapsed into single spaces inside,
+/// so "  Boost   Die " becomes "Boost Die". The length limit counts characters roughly as they're
+/// seen rather than bytes, so accents, combining marks, and emoji (even ones built from several
+/// code points, like flags) only count once. See `char_count()`.
+///
+/// Comparing, hashing, and sorting `Name`s is case-sensitive. For lookups where "boost die"
+/// should find "Boost Die", use `matches()` or key them with `caseless()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Name {
     val: String
 }
@@ -103,11 +151,26 @@ impl Name {
     /// Takes a number and turns it into a String
     pub fn from_num(num: usize) -> Self {
         Name { val: num.to_string() } }
+
+    /// Roughly the number of characters in the name as they're displayed. It's an approximation
+    /// that handles combining marks, emoji modifiers and sequences, and flags, but not every
+    /// script's rules (such as Hangul syllables built from separate jamo). See `grapheme_count()`.
+    pub fn char_count(&self) -> usize { grapheme_count(&self.val) }
+
+    /// Checks whether the given text is this name, ignoring case and extra whitespace
+    pub fn matches(&self, other: &str) -> bool {
+        self.caseless() == CaselessName::from_str(other) }
+
+    /// Wraps the name so that it's compared, hashed, and sorted without regard to case, for use as
+    /// a lookup key
+    pub fn caseless(&self) -> CaselessName {
+        CaselessName::from_str(&self.val) }
     
     fn validate(val: String) -> Result<String, NameError> {
-        if val.trim().is_empty() {
+        let val = normalize_whitespace(&val);
+        if val.is_empty() {
             Err(NameError::Empty) }
-        else if val.len() > MAX_NAME_LEN {
+        else if grapheme_count(&val) > MAX_NAME_LEN {
             Err(NameError::TooLong) }
         else { Ok(val) } }
 }
@@ -136,12 +199,93 @@ impl Display for Name {
 }
 
 
+/// A case-insensitive version of a `Name`, made with `Name::caseless()`, for using as a key in
+/// maps and sets or for sorting names alphabetically regardless of case
+#[derive(Clone, Debug)]
+pub struct CaselessName {
+    original: String,
+    folded: String
+}
+impl CaselessName {
+    fn from_str(val: &str) -> Self {
+        let original = normalize_whitespace(val);
+        let folded = original.to_lowercase();
+        Self { original, folded } }
+}
+impl PartialEq for CaselessName {
+    fn eq(&self, other: &Self) -> bool { self.folded == other.folded }
+}
+impl Eq for CaselessName {}
+impl Hash for CaselessName {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.folded.hash(state) }
+}
+impl PartialOrd for CaselessName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for CaselessName {
+    fn cmp(&self, other: &Self) -> Ordering { self.folded.cmp(&other.folded) }
+}
+impl Display for CaselessName {
+    /// Shows the name with its original casing
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.original) }
+}
+
+
+/// Trims the whitespace off the ends and replaces each run of whitespace inside with one space
+fn normalize_whitespace(val: &str) -> String {
+    val.split_whitespace().collect::<Vec<&str>>().join(" ") }
+
+
+/// Approximates how many characters of the text a person would see, rather than counting bytes or
+/// `char`s. It isn't real grapheme cluster segmentation, which needs the full Unicode tables; it
+/// only handles the common cases:
+/// combining marks, variation selectors, and emoji modifiers are counted along with the character
+/// before them, zero-width joiners glue their neighbours together, and pairs of regional
+/// indicators (flags) count once.
+fn grapheme_count(val: &str) -> usize {
+    let mut count = 0;
+    let mut after_joiner = false;
+    let mut unpaired_indicator = false;
+    for c in val.chars() {
+        let code = c as u32;
+        let extends = matches!(code,
+            0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+            | 0xFE00..=0xFE0F | 0xE0100..=0xE01EF | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F | 0x200D);
+        let is_indicator = (0x1F1E6..=0x1F1FF).contains(&code);
+        let pairs_up = is_indicator && unpaired_indicator;
+        if !(extends || after_joiner || pairs_up) {
+            count += 1; }
+        after_joiner = code == 0x200D;
+        unpaired_indicator = is_indicator && !pairs_up; }
+    count }
+
+
 pub trait Unit: Debug + Display {
+    /// Identifies the unit; two units with the same id are treated as the same unit. Get it from
+    /// `units::unique_id()`, or from `units::named_id()` if it needs to stay the same across runs.
+    /// 0 is taken by `DNumUnit`.
     fn id(&self) -> u64;
     /// If the relationship's outcome should be ignored (such as everything being 
     /// cancelled out, i.e. banes and boons totalling to zero), then return an
     /// empty String, and the display system should ignore it.
     fn output_for(&self, total: i32) -> String;
+    /// If the unit sorts its totals into tiers, like `TieredUnit` does, these are the tiers, in
+    /// the order they're checked. Used for stats on how often each tier comes up.
+    fn tiers(&self) -> &[Tier] { &[] }
+    /// The output format for totals that none of its `tiers()` cover, if it has one
+    fn fallback(&self) -> Option<&str> { None }
+    /// How the unit's values are combined across the dice of a pool, or rollers added together.
+    /// Most units add them up.
+    fn aggregation(&self) -> Aggregation { Aggregation::Sum }
+    /// The lowest and highest the unit's total can be once a roll is finished. Most units don't
+    /// have either.
+    fn bounds(&self) -> Bounds { Bounds::default() }
+    /// What the unit's total counts as when a roll doesn't have a `Value` for it at all, such as
+    /// when sorting rolls or filling in the runs it didn't show up in for stats. Most units treat
+    /// it as 0, but a roll-under unit might want it to be high and a negative scale low. It isn't
+    /// used for display, though: a roll's results only show the units it has `Value`s for.
+    fn default_value(&self) -> i32 { 0 }
 }
 
 impl PartialEq for &dyn Unit {
@@ -149,6 +293,62 @@ impl PartialEq for &dyn Unit {
 }
 
 
+/// How the values of a `Unit` are combined across the dice of a pool and the rollers added together
+/// by a `MathRoller`. Everything else, such as modifiers, subtracted rollers, and the dice of an
+/// explosion, is always added and subtracted normally, so "highest die + 1" works the way you'd
+/// expect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Adds the values together
+    #[default]
+    Sum,
+    /// Keeps the highest value, like Blades in the Dark taking the highest die
+    Max,
+    /// Keeps the lowest value
+    Min,
+    /// Counts how many rolled faces have the unit at all (with a non-zero value), no matter how
+    /// much each face is worth
+    Count,
+    /// 1 if any rolled face has the unit (with a non-zero value), otherwise 0
+    Any,
+    /// 1 if every rolled face that lists the unit has a non-zero value for it, otherwise 0
+    All,
+}
+impl Aggregation {
+    /// Combines a running total with another value
+    pub fn combine(&self, total: i32, value: i32) -> i32 {
+        match self {
+            Aggregation::Sum | Aggregation::Count => total + value,
+            Aggregation::Max => total.max(value),
+            Aggregation::Min => total.min(value),
+            Aggregation::Any => total.max(value.signum().abs()),
+            Aggregation::All => total.min(value.signum().abs()) } }
+
+    /// Turns the value on a rolled face into what it counts as. Only `Count`, `Any`, and `All`
+    /// change it, into 1 or 0 for whether it's there.
+    pub fn rolled(&self, value: i32) -> i32 {
+        match self {
+            Aggregation::Count | Aggregation::Any | Aggregation::All => value.signum().abs(),
+            _ => value } }
+}
+
+
+/// The floor and ceiling of a `Unit`'s total, such as damage never going below 0. They're only
+/// applied to the final totals of a roll, not the totals of the rolls inside it, so "7 - 9 + 5"
+/// floored at 0 is still 3. The roll's `intermediate_results()` shows what was clamped away.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    pub floor: Option<i32>,
+    pub ceiling: Option<i32>,
+}
+impl Bounds {
+    /// Raises the total to the floor or lowers it to the ceiling, if it's past either of them
+    pub fn clamp(&self, total: i32) -> i32 {
+        let total = self.floor.map_or(total, |floor| total.max(floor));
+        self.ceiling.map_or(total, |ceiling| total.min(ceiling)) }
+}
+
+
 /// `Value` is essentially a key-value entry tying a number to a `Relationship`
 /// It's first main use is for `Face` to hold what values it's worth. Its second
 /// is for `Roll`s and totalling up their values.
@@ -163,8 +363,10 @@ impl Value {
     
     fn add(&mut self, other: i32) {
         self.value += other; }
+
+    fn aggregate(&mut self, other: i32) {
+        self.value = self.unit.aggregation().combine(self.value, other); }
     
-    // TODO: determine if this is used
     fn subtract(&mut self, other: i32) {
         self.value -= other; } 
     
@@ -200,7 +402,7 @@ impl Neg for Value {
 
     /// Inverts the value within
     fn neg(self) -> Self::Output {
-        Value { unit: self.unit, value: self.value * -1 } }
+        Value { unit: self.unit, value: -self.value } }
 }
 
 
@@ -254,27 +456,66 @@ impl Values {
     pub fn add_all_values(&mut self, values: Values) {
         for value in values.into_iter() {
             self.add_value(value.clone()); } }
+
+    /// Like `add_value()`, except that the value is combined with an existing one using the
+    /// `Unit`'s `Aggregation`. This is for totalling up the dice of a pool.
+    pub fn aggregate_value(&mut self, value: Value) {
+        for val in self.values.iter_mut() {
+            if val.has_same_unit(&value) {
+                val.aggregate(value.value);
+                return; } }
+        self.values.push(value); }
+
+    /// Like `add_all_values()`, except that the values are combined with existing ones using their
+    /// `Unit`s' `Aggregation`s. This is for totalling up the dice of a pool.
+    pub fn aggregate_all_values(&mut self, values: Values) {
+        for value in values.into_iter() {
+            self.aggregate_value(value); } }
     
     /// Subtract the value of the given `Value`. If there's an existing `Value` with the same `Unit`,
-    /// it subtracts the value of the given `Value` from the existing one. If there isn't, a 
-    /// negative version of the given `Value` is inserted.
+    /// it subtracts the value of the given `Value` from the existing one. If there isn't, a negative
+    /// version of the given `Value` is inserted.
     pub fn subtract_value(&mut self, value: Value) {
-        self.add_value(-value); }
+        for val in self.values.iter_mut() {
+            if val.has_same_unit(&value) {
+                val.subtract(value.value);
+                return; } }
+        self.values.push(-value); }
     
     /// Subtract the values of the all the `Value`s. If there are existing`Value`s with the same 
     /// `Unit`, it subtracts the value of the given `Value`s from the existing ones. For those that
     /// don't have an existing `Value` that matches the `Unit`, it inserts a negative version of the
     /// `Value`.
     pub fn subtract_all_values(&mut self, values: Values) {
-        self.add_all_values(-values); }
+        for value in values.into_iter() {
+            self.subtract_value(value); } }
     
-    /// Returns the `Value` that has the same `Unit` as the given one, if any.
+    /// Returns the `Value` that has the same `Unit` as the given one, if any. Use `total_for()` to
+    /// fall back on the `Unit`'s `default_value()` instead.
     pub fn value_for(&self, unit: &Rc<dyn Unit>) -> Option<i32> {
         for value in &self.values {
-            if value.is_for_unit(&unit) {
+            if value.is_for_unit(unit) {
                 return Some(value.value) } }
         None }
+
+    /// Returns the total for the given `Unit`, or its `default_value()` if there isn't a `Value`
+    /// for it
+    pub fn total_for(&self, unit: &Rc<dyn Unit>) -> i32 {
+        self.value_for(unit).unwrap_or_else(|| unit.default_value()) }
     
+    /// Returns a copy of these `Values` as they count when rolled on a `Face`, using each `Unit`'s
+    /// `Aggregation::rolled()`
+    pub fn as_rolled(&self) -> Values {
+        Values { values: self.values.iter()
+            .map(|value| Value { unit: value.unit.clone(), value: value.unit.aggregation().rolled(value.value) })
+            .collect() } }
+
+    /// Returns a copy of these `Values` with each total clamped to its `Unit`'s `Bounds`
+    pub fn clamped(&self) -> Values {
+        Values { values: self.values.iter()
+            .map(|value| Value { unit: value.unit.clone(), value: value.unit.bounds().clamp(value.value) })
+            .collect() } }
+
     /// Creates an unnamed ValueRoller from the `Values`
     pub fn to_roller(self) -> Rc<dyn SubRoller> {
         ModifierRoller::unnamed(self) }
@@ -310,6 +551,9 @@ impl FromIterator<Values> for Values {
             Values::new(), 
             |mut a, b| {a.add_all_values(b); a}) }
 }
+impl Default for Values {
+    fn default() -> Self { Self::new() }
+}
 impl From<Value> for Values {
     fn from(value: Value) -> Self {
         Self { values: vec![value] } }
@@ -330,3 +574,147 @@ impl Display for Values {
             .join("\n");
         f.write_str(&text) }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, num::NonZero, rc::Rc};
+    use crate::{
+        Aggregation, Name, NameError, Unit, Value, Values, is_valid_name_literal,
+        dice::{Die, Face},
+        random::with_seed,
+        rollers::{PoolRoller, Strategy, SubRoll, SubRoller},
+        units::BasicUnit };
+
+    #[test]
+    fn names_count_characters_not_bytes() {
+        // 35 accented characters is 70 bytes
+        assert!(Name::new("é".repeat(35)).is_ok());
+        assert!(matches!(Name::new("é".repeat(36)), Err(NameError::TooLong)));
+        let flags = Name::new("🇨🇦🇯🇵 e\u{301} 👩\u{200D}🔬").unwrap();
+        assert_eq!(flags.char_count(), 6); }
+
+    #[test]
+    fn names_normalize_whitespace() {
+        assert_eq!(Name::new("  Boost \t  Die ").unwrap().to_string(), "Boost Die");
+        assert!(matches!(Name::new(" \n "), Err(NameError::Empty))); }
+
+    #[test]
+    fn name_literals_are_checked_like_names() {
+        for val in ["Boost Die", "é", "  Boost  Die  ", "\u{3000}\u{3000}", " \n ", "", "é".repeat(35).leak(), "é".repeat(36).leak()] {
+            if is_valid_name_literal(val) {
+                assert!(Name::new(val).is_ok(), "{:?}", val); } }
+        assert!(!is_valid_name_literal("\u{3000}\u{a0}"));
+        assert!(is_valid_name_literal("\u{3000}Boost\u{3000}")); }
+
+    #[test]
+    fn caseless_lookups() {
+        let name = Name::new("Boost Die").unwrap();
+        assert_ne!(name, Name::new("boost die").unwrap());
+        assert!(name.matches("  BOOST  die"));
+        let names: HashSet<_> = [name.caseless()].into_iter().collect();
+        assert!(names.contains(&Name::new("BOOST DIE").unwrap().caseless())); }
+
+    fn unit(aggregation: Aggregation) -> Rc<dyn Unit> {
+        BasicUnit::new(Name::new("Pips").unwrap(), "{}", false).aggregated_by(aggregation) }
+
+    fn combined(aggregation: Aggregation, amounts: &[i32]) -> Option<i32> {
+        let unit = unit(aggregation);
+        let mut values = Values::new();
+        for amount in amounts {
+            values.aggregate_all_values(Values::from(Value { unit: unit.clone(), value: *amount }).as_rolled()); }
+        values.value_for(&unit) }
+
+    #[test]
+    fn values_follow_aggregation() {
+        let amounts = [3, 0, 5, 2];
+        assert_eq!(combined(Aggregation::Sum, &amounts), Some(10));
+        assert_eq!(combined(Aggregation::Max, &amounts), Some(5));
+        assert_eq!(combined(Aggregation::Min, &amounts), Some(0));
+        assert_eq!(combined(Aggregation::Count, &amounts), Some(3));
+        assert_eq!(combined(Aggregation::Any, &amounts), Some(1));
+        assert_eq!(combined(Aggregation::All, &amounts), Some(0));
+        assert_eq!(combined(Aggregation::All, &[3, 5]), Some(1)); }
+
+    #[test]
+    fn pools_take_highest_die() {
+        let unit = unit(Aggregation::Max);
+        let faces = (1..=6)
+            .map(|num| Face::with_one_val(Name::from_num(num as usize), Value { unit: unit.clone(), value: num }))
+            .collect();
+        let pool = Die::new(Name::new("d6").unwrap(), faces).n_times(4);
+        let roll = pool.inner_roll_with(with_seed(9));
+        let highest = roll.rolled_faces().iter()
+            .map(|face| face.totals().value_for(&unit).unwrap())
+            .max();
+        assert_eq!(roll.totals().value_for(&unit), highest); }
+
+    #[test]
+    fn modifiers_are_added_to_the_aggregate() {
+        for (aggregation, expected) in [(Aggregation::Max, 6), (Aggregation::Min, 1), (Aggregation::Any, 1)] {
+            let unit = unit(aggregation);
+            let faces = [1, 6].into_iter()
+                .map(|num| Face::with_one_val(Name::from_num(num as usize), Value { unit: unit.clone(), value: num }))
+                .collect::<Vec<_>>();
+            let pool = PoolRoller::new(Die::new(Name::new("d6").unwrap(), faces), 2, Strategy::KeepAll).unwrap();
+            let plus = pool.clone().plus_modifier(Values::from(Value { unit: unit.clone(), value: 1 }));
+            let minus = pool.minus_modifier(Values::from(Value { unit: unit.clone(), value: 1 }));
+            let seed = (0..)
+                .find(|seed| plus.clone().inner_roll_with(with_seed(*seed)).rolled_faces().iter()
+                    .map(|roll| roll.face().value_for(&unit))
+                    .collect::<HashSet<_>>()
+                    .len() == 2)
+                .unwrap();
+            assert_eq!(plus.inner_roll_with(with_seed(seed)).totals().value_for(&unit), Some(expected + 1));
+            assert_eq!(minus.inner_roll_with(with_seed(seed)).totals().value_for(&unit), Some(expected - 1)); } }
+
+    #[test]
+    fn added_rollers_follow_aggregation() {
+        let unit = unit(Aggregation::Max);
+        let faces = (1..=6)
+            .map(|num| Face::with_one_val(Name::from_num(num as usize), Value { unit: unit.clone(), value: num }))
+            .collect();
+        let die = Die::new(Name::new("d6").unwrap(), faces);
+        let sum = die.clone().plus(die.clone());
+        let plus = Rc::new(Rc::into_inner(die.clone().plus(die))
+            .unwrap()
+            .plus_modifier(Values::from(Value { unit: unit.clone(), value: 1 })));
+        for seed in 0..20 {
+            let roll = sum.clone().inner_roll_with(with_seed(seed));
+            let highest = roll.rolled_faces().iter()
+                .map(|face| face.totals().value_for(&unit).unwrap())
+                .max();
+            assert_eq!(roll.totals().value_for(&unit), highest);
+            let modified = plus.clone().inner_roll_with(with_seed(seed));
+            assert_eq!(modified.totals().value_for(&unit), highest.map(|highest| highest + 1)); } }
+
+    #[test]
+    fn missing_values_use_the_default() {
+        // roll-under, where a miss is worse than any face
+        let unit: Rc<dyn Unit> = BasicUnit::new(Name::new("Roll Under").unwrap(), "{}", false).defaulting_to(7);
+        assert_eq!(Values::new().total_for(&unit), 7);
+        let faces = vec![
+            Face::new(Name::new("Miss").unwrap(), vec![]),
+            Face::with_one_val(Name::from_num(1), Value { unit: unit.clone(), value: 1 })];
+        let die = Die::new(Name::new("Coin").unwrap(), faces);
+        let pool = PoolRoller::new(die, 2, Strategy::DropHighest { count: 1, order_by: vec![unit.clone()] }).unwrap();
+        let stats = pool.get_stats(NonZero::new(4_000).unwrap()).statistics_roll_with(with_seed(3));
+
+        // a miss is only kept if both rolls missed
+        assert!((stats.probability_at_least(&unit, 7) - 0.25).abs() < 0.03);
+        assert_eq!(stats.collected_stats().for_unit(unit).unwrap().max, 7); }
+
+    #[test]
+    fn rolling_0_is_not_the_default() {
+        let unit: Rc<dyn Unit> = BasicUnit::new(Name::new("Roll Under").unwrap(), "{}", false).defaulting_to(7);
+        let faces = vec![
+            Face::new(Name::new("Miss").unwrap(), vec![]),
+            Face::with_one_val(Name::from_num(0), Value { unit: unit.clone(), value: 0 })];
+        let die = Die::new(Name::new("Coin").unwrap(), faces);
+        let stats = die.get_stats(NonZero::new(4_000).unwrap()).statistics_roll_with(with_seed(3));
+
+        assert!((stats.probability_of(&unit, 7) - 0.5).abs() < 0.03);
+        assert!((stats.probability_of(&unit, 0) - 0.5).abs() < 0.03);
+        let histogram = &stats.collected_stats().for_unit(unit.clone()).unwrap().histogram;
+        assert_eq!(stats.probability_of(&unit, 0), histogram.proportion_of(0)); }
+}
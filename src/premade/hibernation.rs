@@ -1,9 +1,8 @@
 use std::rc::Rc;
 use crate::{
-    Name, Unit, Value, clone_vec,
-    dice::{Die, Face}, 
-    units::BasicUnit,
-    storage::{DieStorage, SetId} };
+    Unit, dice_set, name,
+    dice::Die,
+    units::BasicUnit };
 
 // pub fn get(storage: impl DieStorage) -> (Rc<dyn Unit>, Rc<Die>) {
 //     // check if storage contains it. If it does, rebuild it from that
@@ -16,27 +15,19 @@ use crate::{
 // } 
 
 
-pub fn build() -> (Rc<dyn Unit>, Rc<Die>) { 
-    let unit = unit();
-    let die = Die::new("Hibernation", faces(&unit));
-    (unit, die) }
-
-
-fn name(name: &str) -> Name {
-    name.try_into().unwrap() }
-
-
-fn unit() -> Rc<dyn Unit> {
-    BasicUnit::new(name("Successes"), "{} Successes", false) }
-
-
-fn faces(unit: &Rc<dyn Unit>) -> Vec<Rc<Face>> {
-    let face_plus = Face::with_one_val(name("+"), Value::new(unit, 1));
-    let face_minus = Face::with_one_val(name("-"), Value::new(unit, -1));
-    clone_vec![
-        face_plus,
-        face_plus,
-        face_plus,
-        face_minus,
-        face_minus,
-        Face::blank(unit)] }
\ No newline at end of file
+pub fn build() -> (Rc<dyn Unit>, Rc<Die>) {
+    let (units, dice) = dice_set! {
+        units {
+            successes = BasicUnit::new(name!("Successes"), "{} Successes", false)
+                .in_namespace("hibernation").unwrap(),
+        }
+        faces {
+            plus = "+" { successes: 1 },
+            minus = "-" { successes: -1 },
+            blank = "_" { successes: 0 },
+        }
+        dice {
+            "Hibernation": [plus * 3, minus * 2, blank],
+        }
+    };
+    (units[0].clone(), dice[0].clone()) }
@@ -1,141 +1,65 @@
 use std::rc::Rc;
-use crate::{Unit, Value, dice::{Die, Face}, units::{BasicUnit, TieredUnit}, clone_vec, Name};
+use crate::{Unit, dice::Die, presentation::Colour, units::TieredUnit, dice_set, name};
 
 type RUnit = Rc<dyn Unit>;
-type RFace = Rc<Face>;
 type RDie = Rc<Die>;
 
 
-pub fn build() -> (Vec<RUnit>, Vec<RDie>) { 
-    let units = units();
-    let (succ_unit, adv_unit, triumph_unit, force_unit) = units.clone();
-    let (succ_face, succx2_face, fail_face, failx2_face, adv_face, advx2_face, threat_face, threatx2_face, succ_adv_face, fail_threat_face, blank_face) = common_faces(&succ_unit, &adv_unit);
-    let dice: Vec<RDie> = vec![
-        ability_die(&succ_face, &succx2_face, &adv_face, &advx2_face, &succ_adv_face, &blank_face),
-        proficiency_die(&succ_face, &succx2_face, &adv_face, &advx2_face, &succ_adv_face, &blank_face, &triumph_unit),
-        boost_die(&succ_face, &adv_face, &advx2_face, &succ_adv_face, &blank_face),
-        difficulty_die(&fail_face, &failx2_face, &threat_face, &threatx2_face, &fail_threat_face, &blank_face),
-        challenge_die(&fail_face, &failx2_face, &threat_face, &threatx2_face, &fail_threat_face, &blank_face, &triumph_unit),
-        setback_die(&fail_face, &threat_face, &blank_face),
-        force_die(&force_unit) ];
-    let arr_units: [RUnit; 4] = units.into();
-    (Vec::from(&arr_units), dice) }
-
-
-fn name(name: &str) -> Name {
-    name.try_into().unwrap() }
-
-
-fn units() -> (RUnit, RUnit, RUnit, RUnit) {
-    ( TieredUnit::pos_zero_neg(name("Success"), "{} Successes", "{} Successes", "{|} Failures"),
-    TieredUnit::pos_neg(name("Advantage"), "{} Advantage", "{|} Threat"),
-    TieredUnit::pos_neg(name("Triumph"), "{} Triumph", "{|} Despair"),
-    TieredUnit::pos_neg(name("Force"), "{} Light Side", "{|} Dark Side") ) }
-
-
-fn common_faces(success_unit: &RUnit, adv_unit: &RUnit) -> (RFace, RFace, RFace, RFace, RFace, RFace, RFace, RFace, RFace, RFace, RFace) {
-    ( Face::with_one_val(name("Success"), Value::new(success_unit, 1)),
-    Face::with_one_val(name("Success x2"), Value::new(success_unit, 2)),
-    Face::with_one_val(name("Failure"), Value::new(success_unit, -1)),
-    Face::with_one_val(name("Failure x2"), Value::new(success_unit, -2)),
-    Face::with_one_val(name("Advantage"), Value::new(adv_unit, 1)),
-    Face::with_one_val(name("Advantage x2"), Value::new(adv_unit, 2)),
-    Face::with_one_val(name("Threat"), Value::new(adv_unit, -1)),
-    Face::with_one_val(name("Threat x2"), Value::new(adv_unit, -2)),
-    Face::with_two_vals(name("Success + Advantage"), Value::new(success_unit, 1), Value::new(adv_unit, 1)),
-    Face::with_two_vals(name("Failure + Threat"), Value::new(success_unit, -1), Value::new(adv_unit, -1)),
-    Face::blank(success_unit)) }
-
-
-fn ability_die(succ_face: &RFace, succx2_face: &RFace, adv_face: &RFace, advx2_face: &RFace, succ_adv_face: &RFace, blank_face: &RFace) -> RDie {
-    Die::new("Ability", clone_vec![
-        succ_face,
-        succ_face,
-        succx2_face,
-        adv_face,
-        adv_face,
-        succ_adv_face,
-        advx2_face,
-        blank_face ]) }
-
-
-fn proficiency_die(succ_face: &RFace, succx2_face: &RFace, adv_face: &RFace, advx2_face: &RFace, succ_adv_face: &RFace, blank_face: &RFace, triumph_unit: &RUnit) -> RDie {
-    Die::new("Proficiency", clone_vec![
-        succ_face,
-        succ_face,
-        succx2_face,
-        succx2_face,
-        adv_face,
-        adv_face,
-        adv_face,
-        succ_adv_face,
-        succ_adv_face,
-        succ_adv_face,
-        Face::with_one_val(name("Triumph"), Value::new(triumph_unit, 1)),
-        blank_face ]) }
-
-
-fn boost_die(succ_face: &RFace, adv_face: &RFace, advx2_face: &RFace, succ_adv_face: &RFace, blank_face: &RFace) -> RDie {
-    Die::new("Boost", clone_vec![
-        succ_face,
-        succ_adv_face,
-        adv_face,
-        advx2_face,
-        blank_face,
-        blank_face ]) }
-
-
-fn difficulty_die(fail_face: &RFace, failx2_face: &RFace, threat_face: &RFace, threatx2_face: &RFace, fail_threat_face: &RFace, blank_face: &RFace) -> RDie {
-    Die::new("Difficulty", clone_vec![
-        fail_face,
-        failx2_face,
-        threat_face,
-        threat_face,
-        threat_face,
-        threatx2_face,
-        fail_threat_face,
-        blank_face]) }
-
-
-fn challenge_die(fail_face: &RFace, failx2_face: &RFace, threat_face: &RFace, threatx2_face: &RFace, fail_threat_face: &RFace, blank_face: &RFace, triumph_unit: &RUnit) -> RDie {
-    Die::new("Challenge", clone_vec![
-        fail_face,
-        failx2_face,
-        threat_face,
-        threat_face,
-        threat_face,
-        threatx2_face,
-        fail_threat_face,
-        blank_face ]) }
-
-
-fn setback_die(fail_face: &RFace, threat_face: &RFace, blank_face: &RFace) -> RDie {
-    Die::new("Setback", clone_vec![
-        fail_face,
-        fail_face,
-        threat_face,
-        threat_face,
-        blank_face,
-        blank_face
-    ])
-}
-
-
-fn force_die(force_unit: &RUnit) -> RDie {
-    let light = Face::with_one_val(name("Light"), Value::new(force_unit, 1));
-    let lightx2 = Face::with_one_val(name("Light x2"), Value::new(force_unit, 2));
-    let dark = Face::with_one_val(name("Dark"), Value::new(force_unit, -1));
-    let darkx2 = Face::with_one_val(name("Dark x2"), Value::new(force_unit, -2));
-    Die::new("Force", clone_vec![
-        light,
-        light,
-        lightx2,
-        lightx2,
-        lightx2,
-        dark,
-        dark,
-        dark,
-        dark,
-        dark,
-        dark,
-        darkx2 ]) }
\ No newline at end of file
+/// The colours of the dice, in the same order as the dice
+const COLOURS: [Colour; 7] = [
+    Colour::rgb(0x3a, 0x9a, 0x3a),  // Ability
+    Colour::rgb(0xf2, 0xc9, 0x1d),  // Proficiency
+    Colour::rgb(0x7f, 0xc8, 0xf0),  // Boost
+    Colour::rgb(0x6b, 0x2d, 0x8c),  // Difficulty
+    Colour::rgb(0xc4, 0x1e, 0x2a),  // Challenge
+    Colour::rgb(0x1a, 0x1a, 0x1a),  // Setback
+    Colour::rgb(0xf5, 0xf5, 0xf5),  // Force
+];
+
+
+pub fn build() -> (Vec<RUnit>, Vec<RDie>) {
+    let (units, dice) = dice_set! {
+        units {
+            success = TieredUnit::pos_zero_neg(name!("Success"), "{} Successes", "{} Successes", "{|} Failures")
+                .in_namespace("star_wars").unwrap(),
+            advantage = TieredUnit::pos_neg(name!("Advantage"), "{} Advantage", "{|} Threat")
+                .in_namespace("star_wars").unwrap(),
+            triumph = TieredUnit::pos_neg(name!("Triumph"), "{} Triumph", "{|} Despair")
+                .in_namespace("star_wars").unwrap(),
+            force = TieredUnit::pos_neg(name!("Force"), "{} Light Side", "{|} Dark Side")
+                .in_namespace("star_wars").unwrap(),
+        }
+        faces {
+            succ = "Success" as "success" { success: 1 },
+            succ_x2 = "Success x2" as "success_x2" { success: 2 },
+            fail = "Failure" as "failure" { success: -1 },
+            fail_x2 = "Failure x2" as "failure_x2" { success: -2 },
+            adv = "Advantage" as "advantage" { advantage: 1 },
+            adv_x2 = "Advantage x2" as "advantage_x2" { advantage: 2 },
+            threat = "Threat" as "threat" { advantage: -1 },
+            threat_x2 = "Threat x2" as "threat_x2" { advantage: -2 },
+            succ_adv = "Success + Advantage" as "success_advantage" { success: 1, advantage: 1 },
+            fail_threat = "Failure + Threat" as "failure_threat" { success: -1, advantage: -1 },
+            triumph_face = "Triumph" as "triumph" { triumph: 1 },
+            light = "Light" as "light" { force: 1 },
+            light_x2 = "Light x2" as "light_x2" { force: 2 },
+            dark = "Dark" as "dark" { force: -1 },
+            dark_x2 = "Dark x2" as "dark_x2" { force: -2 },
+            blank = "_" as "blank" { success: 0 },
+        }
+        dice {
+            "Ability": [succ * 2, succ_x2, adv * 2, succ_adv, adv_x2, blank],
+            "Proficiency": [succ * 2, succ_x2 * 2, adv * 3, succ_adv * 3, triumph_face, blank],
+            "Boost": [succ, succ_adv, adv, adv_x2, blank * 2],
+            "Difficulty": [fail, fail_x2, threat * 3, threat_x2, fail_threat, blank],
+            "Challenge": [fail, fail_x2, threat * 3, threat_x2, fail_threat, blank],
+            "Setback": [fail * 2, threat * 2, blank * 2],
+            "Force": [light * 2, light_x2 * 3, dark * 6, dark_x2],
+        }
+    };
+    assert_eq!(dice.len(), COLOURS.len(), "every die needs a colour");
+    let dice = dice.into_iter()
+        .zip(COLOURS)
+        .map(|(die, colour)| die.coloured(colour))
+        .collect();
+    (units, dice) }
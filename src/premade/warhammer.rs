@@ -1,123 +1,70 @@
 use std::rc::Rc;
-use crate::{Unit, Value, dice::{Die, Face}, units::{BasicUnit, TieredUnit}, clone_vec, Name};
+use crate::{Unit, dice::Die, presentation::Colour, units::{BasicUnit, TieredUnit}, dice_set, name};
 
 type RUnit = Rc<dyn Unit>;
-type RFace = Rc<Face>;
 type RDie = Rc<Die>;
 
 
-pub fn build() -> (Vec<RUnit>, Vec<RDie>) { 
-    let units = units();
-    let (success, boon, delay, exert, comet, star, reroll) = units.clone();
-    let (challenge_face, success_face, succ_boon_face, bane_face, boon_face, blank_face) = common_faces(&success, &boon);
-    let dice: Vec<RDie> = vec![
-        characteristic_die(&success_face, &boon_face, &blank_face),
-        challenge_die(&challenge_face, &bane_face, &blank_face, &success, &boon, &star),
-        expertise_die(&success_face, &boon_face, &blank_face, &success, &reroll, &comet),
-        fortune_die(&success_face, &boon_face, &blank_face),
-        misfortune_die(&challenge_face, &bane_face, &blank_face),
-        conservative_die(&success_face, &boon_face, &succ_boon_face, &blank_face, &success, &delay),
-        reckless_die(&succ_boon_face, &bane_face, &blank_face, &success, &boon, &exert) ];
-    let arr_units: [RUnit; 7] = units.into();
-    (Vec::from(&arr_units), dice) }
-
-
-fn name(name: &str) -> Name {
-    name.try_into().unwrap() }
-
-
-fn units() -> (RUnit, RUnit, RUnit, RUnit, RUnit, RUnit, RUnit) {
-    ( TieredUnit::pos_zero_neg(name("Successes"), "{} Successes", "{} Successes", "{|} Challenges"),
-    TieredUnit::pos_neg(name("Boons"), "{} Boons", "{|} Banes"),
-    BasicUnit::new(name("Delay"), "{} Delay(s)", true),
-    BasicUnit::new(name("Exertion"), "{} Exertion", true),
-    BasicUnit::new(name("Sigmar's Comet"), "{} Sigmar's Comet(s)", true),
-    BasicUnit::new(name("Chaos Star"), "{} Chaos Star(s)", true),
-    BasicUnit::new(name("Reroll"), "Reroll {} Expertise Dice", true) ) }
-
-
-fn common_faces(success_unit: &RUnit, boon_unit: &RUnit) -> (RFace, RFace, RFace, RFace, RFace, RFace) {
-    ( Face::with_one_val(name("Challenge"), Value::new(success_unit, -1)),
-    Face::with_one_val(name("Success"), Value::new(success_unit, 1)),
-    Face::with_two_vals(name("Success + Boon"), Value::new(success_unit, 1), Value::new(boon_unit, 1)),
-    Face::with_one_val(name("Bane"), Value::new(boon_unit, -1)),
-    Face::with_one_val(name("Boon"), Value::new(boon_unit, 1)),
-    Face::blank(success_unit)) }
-
-
-fn characteristic_die(success: &RFace, boon: &RFace, blank: &RFace) -> RDie {
-    Die::new("Characteristic", clone_vec![success, success, boon, blank]) }
-
-
-fn challenge_die(challenge: &RFace, bane: &RFace, blank: &RFace, succ_unit: &RUnit, boon_unit: &RUnit, star_unit: &RUnit) -> RDie {
-    let challenge_x2 = Face::with_one_val(name("Challenge x2"), Value::new(&succ_unit, -2));
-    Die::new("Challenge", clone_vec![
-        challenge,
-        challenge,
-        challenge_x2,
-        challenge_x2,
-        bane,
-        Face::with_one_val(name("Bane x2"), Value::new(&boon_unit, -2)),
-        Face::with_one_val(name("Chaos Star"), Value::new(&star_unit, 1)),
-        blank]) }
-
-
-fn expertise_die(success: &RFace, boon: &RFace, blank: &RFace, succ_unit: &RUnit, reroll_unit: &RUnit, comet_unit: &RUnit) -> RDie {
-    let righteous = Face::with_two_vals(name("Righteous Success"), Value::new(succ_unit, 1), Value::new(reroll_unit, 1));
-    Die::new("Expertise", clone_vec![
-        success,
-        righteous,
-        boon,
-        boon,
-        Face::with_one_val(name("Sigmar's Comet"), Value::new(&comet_unit, 1)),
-        blank]) }
-
-
-fn fortune_die(success: &RFace, boon: &RFace, blank: &RFace) -> RDie {
-    Die::new("Fortune", clone_vec![
-        success,
-        success,
-        boon,
-        blank,
-        blank,
-        blank]) }
-
-
-fn misfortune_die(challenge: &RFace, bane: &RFace, blank: &RFace) -> RDie {
-    Die::new("Misfortune", clone_vec![
-        challenge,
-        challenge,
-        bane,
-        blank,
-        blank,
-        blank]) }
-
-
-fn conservative_die(success: &RFace, boon: &RFace, succ_boon: &RFace, blank: &RFace, succ_unit: &RUnit, delay_unit: &RUnit) -> RDie {
-    let succ_delay = Face::with_two_vals(name("Success + Delay"), Value::new(&succ_unit, 1), Value::new(&delay_unit, 1));
-    Die::new("Conservative", clone_vec![
-        success,
-        success,
-        success,
-        success,
-        boon,
-        boon,
-        succ_boon,
-        succ_delay,
-        succ_delay,
-        blank]) }
-
-fn reckless_die(succ_boon: &RFace, bane: &RFace, blank: &RFace, succ_unit: &RUnit, boon_unit: &RUnit, exert_unit: &RUnit) -> RDie {
-    let success_x2 = Face::with_one_val(name("Success x2"), Value::new(&succ_unit, 2));
-    let succ_exert = Face::with_two_vals(name("Success + Exertion"), Value::new(&succ_unit, 1), Value::new(&exert_unit, 1));
-    Die::new("Reckless", clone_vec![
-        success_x2,
-        success_x2,
-        &Face::with_one_val(name("Boon x2"), Value::new(&boon_unit, 2)),
-        succ_boon,
-        bane,
-        bane,
-        succ_exert,
-        succ_exert,
-        blank,
-        blank]) }
\ No newline at end of file
+/// The colours of the dice, in the same order as the dice
+const COLOURS: [Colour; 7] = [
+    Colour::rgb(0x2b, 0x5c, 0xb8),  // Characteristic
+    Colour::rgb(0x6b, 0x2d, 0x8c),  // Challenge
+    Colour::rgb(0xf2, 0xc9, 0x1d),  // Expertise
+    Colour::rgb(0xf5, 0xf5, 0xf5),  // Fortune
+    Colour::rgb(0x1a, 0x1a, 0x1a),  // Misfortune
+    Colour::rgb(0x3a, 0x9a, 0x3a),  // Conservative
+    Colour::rgb(0xc4, 0x1e, 0x2a),  // Reckless
+];
+
+
+pub fn build() -> (Vec<RUnit>, Vec<RDie>) {
+    let (units, dice) = dice_set! {
+        units {
+            success = TieredUnit::pos_zero_neg(name!("Successes"), "{} Successes", "{} Successes", "{|} Challenges")
+                .in_namespace("warhammer").unwrap(),
+            boon = TieredUnit::pos_neg(name!("Boons"), "{} Boons", "{|} Banes")
+                .in_namespace("warhammer").unwrap(),
+            delay = BasicUnit::new(name!("Delay"), "{} Delay(s)", true)
+                .in_namespace("warhammer").unwrap(),
+            exertion = BasicUnit::new(name!("Exertion"), "{} Exertion", true)
+                .in_namespace("warhammer").unwrap(),
+            comet = BasicUnit::new(name!("Sigmar's Comet"), "{} Sigmar's Comet(s)", true)
+                .in_namespace("warhammer").unwrap(),
+            star = BasicUnit::new(name!("Chaos Star"), "{} Chaos Star(s)", true)
+                .in_namespace("warhammer").unwrap(),
+            reroll = BasicUnit::new(name!("Reroll"), "Reroll {} Expertise Dice", true)
+                .in_namespace("warhammer").unwrap(),
+        }
+        faces {
+            challenge_face = "Challenge" as "challenge" { success: -1 },
+            challenge_x2 = "Challenge x2" as "challenge_x2" { success: -2 },
+            success_face = "Success" as "success" { success: 1 },
+            success_x2 = "Success x2" as "success_x2" { success: 2 },
+            succ_boon = "Success + Boon" as "success_boon" { success: 1, boon: 1 },
+            bane = "Bane" as "bane" { boon: -1 },
+            bane_x2 = "Bane x2" as "bane_x2" { boon: -2 },
+            boon_face = "Boon" as "boon" { boon: 1 },
+            boon_x2 = "Boon x2" as "boon_x2" { boon: 2 },
+            chaos_star = "Chaos Star" as "chaos_star" { star: 1 },
+            righteous = "Righteous Success" as "righteous_success" { success: 1, reroll: 1 },
+            sigmars_comet = "Sigmar's Comet" as "sigmars_comet" { comet: 1 },
+            succ_delay = "Success + Delay" as "success_delay" { success: 1, delay: 1 },
+            succ_exert = "Success + Exertion" as "success_exertion" { success: 1, exertion: 1 },
+            blank = "_" as "blank" { success: 0 },
+        }
+        dice {
+            "Characteristic": [success_face * 2, boon_face, blank],
+            "Challenge": [challenge_face * 2, challenge_x2 * 2, bane, bane_x2, chaos_star, blank],
+            "Expertise": [success_face, righteous, boon_face * 2, sigmars_comet, blank],
+            "Fortune": [success_face * 2, boon_face, blank * 3],
+            "Misfortune": [challenge_face * 2, bane, blank * 3],
+            "Conservative": [success_face * 4, boon_face * 2, succ_boon, succ_delay * 2, blank],
+            "Reckless": [success_x2 * 2, boon_x2, succ_boon, bane * 2, succ_exert * 2, blank * 2],
+        }
+    };
+    assert_eq!(dice.len(), COLOURS.len(), "every die needs a colour");
+    let dice = dice.into_iter()
+        .zip(COLOURS)
+        .map(|(die, colour)| die.coloured(colour))
+        .collect();
+    (units, dice) }
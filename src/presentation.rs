@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+
+/// A colour for drawing a die, given as red, green, and blue
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+impl Colour {
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue } }
+
+    /// Reads a colour written as "#rrggbb" (the "#" is optional). Returns None if it isn't one.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) { return None }
+        let channel = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+        Some(Self { red: channel(0)?, green: channel(2)?, blue: channel(4)? }) }
+
+    /// How bright the colour looks, from 0 to 1. Handy for picking a text colour that stands out
+    /// against it.
+    pub fn brightness(&self) -> f32 {
+        (0.299 * self.red as f32 + 0.587 * self.green as f32 + 0.114 * self.blue as f32) / 255.0 }
+}
+impl Display for Colour {
+    /// Writes it as "#rrggbb"
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)) }
+}
+
+
+/// The physical shape of a die. Dice that don't say otherwise get the shape that matches how
+/// many faces they have (see `for_faces()`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DieShape {
+    Coin,
+    D4,
+    D6,
+    D8,
+    D10,
+    D12,
+    D20,
+    /// Any other number of faces, usually drawn as a round die
+    Other(usize),
+}
+impl DieShape {
+    /// The usual shape for a die with the given number of faces
+    pub fn for_faces(count: usize) -> Self {
+        match count {
+            2 => DieShape::Coin,
+            4 => DieShape::D4,
+            6 => DieShape::D6,
+            8 => DieShape::D8,
+            10 => DieShape::D10,
+            12 => DieShape::D12,
+            20 => DieShape::D20,
+            count => DieShape::Other(count) } }
+
+    /// A short name for the shape, such as "d6" or "coin", used as its key in serialized results
+    pub fn key(&self) -> String {
+        match self {
+            DieShape::Coin => "coin".to_string(),
+            DieShape::D4 => "d4".to_string(),
+            DieShape::D6 => "d6".to_string(),
+            DieShape::D8 => "d8".to_string(),
+            DieShape::D10 => "d10".to_string(),
+            DieShape::D12 => "d12".to_string(),
+            DieShape::D20 => "d20".to_string(),
+            DieShape::Other(count) => format!("d{}", count) } }
+}
+
+
+/// Optional details about how a `Die` should look in a UI. Anything left as None is up to the UI.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiePresentation {
+    pub colour: Option<Colour>,
+    pub shape: Option<DieShape>,
+}
+
+
+/// Optional details about how a `Face` should look in a UI. Anything left as None is up to the UI,
+/// which would usually fall back on the face's label.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FacePresentation {
+    /// Text to draw on the face, such as a symbol from the game's own font
+    pub glyph: Option<String>,
+    /// A key for the UI to look up its own image for the face, such as "success"
+    pub icon: Option<String>,
+    /// A label for tight spaces, such as "S" for "Success"
+    pub short_label: Option<String>,
+    /// A label with everything spelled out, such as "Success and Advantage"
+    pub long_label: Option<String>,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        premade::{number, star_wars},
+        presentation::{Colour, DieShape} };
+
+    #[test]
+    fn colours_and_shapes() {
+        let colour = Colour::from_hex("#3a9A3a").unwrap();
+        assert_eq!(colour, Colour::rgb(0x3a, 0x9a, 0x3a));
+        assert_eq!(colour.to_string(), "#3a9a3a");
+        assert_eq!(Colour::from_hex("3a9a3"), None);
+        assert_eq!(Colour::from_hex("#+f+f+f"), None);
+
+        let (_, dice) = number::build();
+        assert_eq!(dice[3].shape(), DieShape::D6);
+        assert_eq!(dice[8].shape().key(), "d30");
+        assert_eq!(dice[3].clone().shaped(DieShape::D12).shape(), DieShape::D12);
+
+        let (_, star_wars) = star_wars::build();
+        assert_eq!(star_wars[0].presentation.colour, Some(colour));
+        assert_eq!(star_wars[0].shape(), DieShape::D8);
+        assert!(star_wars.iter().all(|die| die.faces.iter().all(|face| face.presentation.icon.is_some())));
+        assert_eq!(star_wars[0].faces[5].presentation.icon.as_deref(), Some("success_advantage")); }
+}
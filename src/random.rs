@@ -4,6 +4,7 @@ use std::{
     sync::Mutex,
     time::Instant };
 use instant_hasher::hash;
+use crate::units::unique_id;
 
 
 const A: u64 = 48_271;
@@ -16,7 +17,7 @@ fn get_u64() -> u64 { hash(Instant::now()) }
 /// Chooses a random element from a `Vec` of `Rc<T>` and returns a clone of it. It COULD be more
 /// generalized, but it was only required for choosing a random face on a `Die`, so I didn't bother
 /// to generalize it any further.
-pub fn choose_from<T>(vec: &Vec<Rc<T>>, rng: &mut Rng) -> Rc<T> {
+pub fn choose_from<T>(vec: &[Rc<T>], rng: &mut Rng) -> Rc<T> {
     vec[rng.next_index(vec.len())].clone() }
 
 
@@ -32,8 +33,9 @@ pub fn with_seed(seed: u64) -> Rng { Rng::from_seed(seed) }
 pub fn test_rng() -> Rng { Rng::from_seed(1) }
 
 
-/// Convenience function that creates a new id for `Unit`s. Kind of a random UUID for them.
-pub fn new_id() -> u64 { get_u64() }
+/// Convenience function that creates a new id for `Unit`s. Kind of a random UUID for them, except
+/// that it's guaranteed to be unique. See `units::unique_id()`.
+pub fn new_id() -> u64 { unique_id() }
 
 
 fn wrap_seed(seed: u64) -> Rc<Mutex<u64>> {
@@ -57,6 +59,7 @@ impl Rng {
         Self { seed: wrap_seed(get_u64()) } }
 
     /// Generates the next random number
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> u64 {
         let mut seed = self.seed.lock().unwrap();
         let next = next(*seed.deref());
@@ -69,6 +72,10 @@ impl Rng {
         let base = (self.next() - 1) as usize;
         base % length }
 }
+impl Default for Rng {
+    /// The same as `new()`
+    fn default() -> Self { Self::new() }
+}
 
 
 mod instant_hasher {
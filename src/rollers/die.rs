@@ -7,20 +7,15 @@ use crate::{
     dice::{Die, Face},
     random::Rng,
     rollers::{Roll, Roller} };
-use crate::rollers::{SubRoll, SubRoller};
+use crate::rollers::{DieStatus, RollWalker, SubRoll, SubRoller, finish::FinishedRoll};
 
 
 impl Roller for Die {
     fn description(&self) -> String {
-        self.name.clone() }
+        self.name.to_string() }
 
     fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
-        let face = self.roll_face_with(rng.clone());
-        let initial_roll = DieRoll::new(self.clone(), face.clone());
-        let mut output_roll: Box<dyn Roll> = initial_roll.clone();
-        if initial_roll.should_explode() {
-            output_roll = initial_roll.explode(rng); }
-        output_roll }
+        FinishedRoll::new(self.inner_roll_with(rng)) }
 }
 impl SubRoller for Die {
     fn is_simple(&self) -> bool { true }
@@ -47,6 +42,12 @@ impl DieRoll {
     pub(crate) fn new(die: Rc<Die>, face: Rc<Face>) -> Box<Self> {
         Box::new(Self{ die, face }) }
 
+    /// The `Die` that was rolled
+    pub fn die(&self) -> &Rc<Die> { &self.die }
+
+    /// The `Face` that came up
+    pub fn face(&self) -> &Rc<Face> { &self.face }
+
     fn should_explode(&self) -> bool {
         if let Some(explode_on) = &self.die.explode_on {
             self.face.value_for(explode_on).is_some() }
@@ -67,6 +68,8 @@ impl Roll for DieRoll {
 
     fn final_result(&self) -> String {
         self.totals().to_string() }
+
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) { walker.die(self) }
 }
 impl SubRoll for DieRoll {
     fn is_simple(&self) -> bool { true }
@@ -74,7 +77,7 @@ impl SubRoll for DieRoll {
     fn rolled_faces(&self) -> Vec<&DieRoll> {
         vec![self] }
 
-    fn totals(&self) -> Values { self.face.deref().values.clone() }
+    fn totals(&self) -> Values { self.face.deref().values.as_rolled() }
 }
 impl Display for DieRoll {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
@@ -117,6 +120,12 @@ impl Roll for ExplodedRoll {
     
     fn final_result(&self) -> String {
         self.totals().to_string() }
+
+    /// The die that exploded is part 0, and the dice rolled because of it follow
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) {
+        walker.part(0, DieStatus::Exploded, self.triggering_roll.as_ref());
+        for (idx, roll) in self.triggered_rolls.iter().enumerate() {
+            walker.part(idx + 1, DieStatus::FromExplosion, roll.as_ref()); } }
 }
 impl SubRoll for ExplodedRoll {
     fn is_simple(&self) -> bool { false }
@@ -154,7 +163,7 @@ mod tests {
         let rel = DNumUnit::new();
         let face1 = Face::new(name("1"), vec![Value{ unit: rel.clone(), value: 1}]);
         let face2 = Face::new(name("2"), vec![Value{ unit: rel.clone(), value: 2}]);
-        Die::new("d2", vec![face1, face2]) }
+        Die::new(name("d2"), vec![face1, face2]) }
 
     fn always_2_rng() -> Rng { Rng::from_seed(2) }
 
@@ -164,7 +173,7 @@ mod tests {
     fn d2_roll_totals() {
         let die: Rc<Die> = d2_test_die();
         let die_roller: Rc<dyn SubRoller> = die.clone();
-        let one: &Values = &die.faces.get(0).unwrap().values;
+        let one: &Values = &die.faces.first().unwrap().values;
         let two: &Values = &die.faces.get(1).unwrap().values;
 
         assert_eq!(die_roller.clone()
@@ -0,0 +1,218 @@
+use std::rc::Rc;
+use crate::{
+    Name, Unit, Value, Values,
+    dice::{Die, Face},
+    presentation::DieShape,
+    random::Rng,
+    rollers::{DieRoll, DieStatus, Roll, RollWalker, Roller, SubRoll, SubRoller, finish::FinishedRoll} };
+
+
+/// `DynamicPoolRoller` is like a `PoolRoller`, except that the number of rolls comes from rolling
+/// another `SubRoller` first and taking its total for a `Unit`, as in "roll 1d4, then roll that
+/// many d6". A total of 0 or less rolls nothing. Since the count isn't stuck in a `u8`, it can go
+/// well past 255 rolls, if the determining roll is big enough, but never past `MAX_COUNT`.
+pub struct DynamicPoolRoller {
+    count: Rc<dyn SubRoller>,
+    count_unit: Rc<dyn Unit>,
+    die: Rc<dyn SubRoller>,
+}
+impl DynamicPoolRoller {
+    /// The most rolls it'll make, no matter how big the determining roll is, so that a total near
+    /// `i32::MAX` doesn't try to roll (and hold onto) billions of dice
+    pub const MAX_COUNT: i32 = u16::MAX as i32;
+
+    /// Rolls `die` as many times as the total of `count_unit` from rolling `count`
+    pub fn new(count: Rc<dyn SubRoller>, count_unit: Rc<dyn Unit>, die: Rc<dyn SubRoller>) -> Rc<Self> {
+        Rc::new(Self { count, count_unit, die }) }
+}
+impl Roller for DynamicPoolRoller {
+    /// The count is always wrapped in parentheses, such as "(1d4)d6", so it can't be mistaken for
+    /// part of the die
+    fn description(&self) -> String {
+        if self.die.is_die() {
+            format!("({}){}", self.count.description(), self.die.description()) }
+        else {
+            format!("({})({})", self.count.description(), self.die.description()) } }
+
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        FinishedRoll::new(self.inner_roll_with(rng)) }
+}
+impl SubRoller for DynamicPoolRoller {
+    fn is_simple(&self) -> bool { false }
+
+    fn inner_roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn SubRoll> {
+        let determining = self.count.clone().inner_roll_with(rng.clone());
+        let count = determining.totals().total_for(&self.count_unit).clamp(0, Self::MAX_COUNT);
+        let rolls = (0..count)
+            .map(|_| self.die.clone().inner_roll_with(rng.clone()))
+            .collect();
+        DeterminedRoll::new(determining, rolls) }
+}
+
+
+/// `DynamicDieRoller` rolls a numeric die whose size comes from rolling another `SubRoller` first
+/// and taking its total for a `Unit`, such as DCC's "d(1d6+4)". The die has faces numbered from 1
+/// up to the size, each worth its own number in `unit`. A size of 0 or less rolls nothing.
+///
+/// Only the face that comes up is made, rather than every face of the die, so the `Die` of the
+/// rolled `DieRoll` has just that one face. Its name and shape still match its size.
+pub struct DynamicDieRoller {
+    size: Rc<dyn SubRoller>,
+    size_unit: Rc<dyn Unit>,
+    unit: Rc<dyn Unit>,
+}
+impl DynamicDieRoller {
+    /// Rolls a die with as many sides as the total of `size_unit` from rolling `size`, with its
+    /// faces worth their numbers in `unit`
+    pub fn new(size: Rc<dyn SubRoller>, size_unit: Rc<dyn Unit>, unit: Rc<dyn Unit>) -> Rc<Self> {
+        Rc::new(Self { size, size_unit, unit }) }
+
+    /// Picks a number from 1 to `size`, the same way a `Die` with that many faces would
+    fn roll_size(&self, size: i32, mut rng: Rng) -> Box<DieRoll> {
+        let num = rng.next_index(size as usize) as i32 + 1;
+        let face = Face::with_one_val(Name::from_num(num as usize), Value::new(&self.unit, num));
+        let die = Die::new(Name::new(format!("d{}", size)).unwrap(), vec![face.clone()])
+            .shaped(DieShape::for_faces(size as usize));
+        DieRoll::new(die, face) }
+}
+impl Roller for DynamicDieRoller {
+    /// The size is always wrapped in parentheses, such as "d(1d6 + 4)"
+    fn description(&self) -> String {
+        format!("d({})", self.size.description()) }
+
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        FinishedRoll::new(self.inner_roll_with(rng)) }
+}
+impl SubRoller for DynamicDieRoller {
+    fn is_simple(&self) -> bool { false }
+
+    fn inner_roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn SubRoll> {
+        let determining = self.size.clone().inner_roll_with(rng.clone());
+        let size = determining.totals().total_for(&self.size_unit);
+        let rolls = if size > 0 {
+            vec![self.roll_size(size, rng) as Box<dyn SubRoll>] }
+        else {
+            Vec::with_capacity(0) };
+        DeterminedRoll::new(determining, rolls) }
+}
+
+
+/// The `Roll` of the dynamic rollers, which holds the roll that determined what was rolled as well
+/// as what was rolled. Only the determined rolls count towards the totals.
+struct DeterminedRoll {
+    determining: Box<dyn SubRoll>,
+    rolls: Vec<Box<dyn SubRoll>>,
+}
+impl DeterminedRoll {
+    fn new(determining: Box<dyn SubRoll>, rolls: Vec<Box<dyn SubRoll>>) -> Box<Self> {
+        Box::new(Self { determining, rolls }) }
+}
+impl Roll for DeterminedRoll {
+    /// Shows the determining roll, then what was rolled because of it, such as
+    /// "d4:[2] => d6:[5] + d6:[1]"
+    fn intermediate_results(&self) -> String {
+        let rolls = if self.rolls.is_empty() {
+            "nothing".to_string() }
+        else {
+            self.rolls.iter()
+                .map(|roll| roll.inner_intermediate_results())
+                .collect::<Vec<String>>()
+                .join(" + ") };
+        format!("{} => {}", self.determining.inner_intermediate_results(), rolls) }
+
+    fn final_result(&self) -> String { self.totals().to_string() }
+
+    /// The determining roll is part 0, and the determined rolls follow
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) {
+        walker.part(0, DieStatus::Determining, self.determining.as_ref());
+        for (idx, roll) in self.rolls.iter().enumerate() {
+            walker.part(idx + 1, DieStatus::Kept, roll.as_ref()); } }
+}
+impl SubRoll for DeterminedRoll {
+    fn is_simple(&self) -> bool { false }
+
+    /// Returns the rolled faces of just the determined rolls
+    fn rolled_faces(&self) -> Vec<&DieRoll> {
+        self.rolls.iter()
+            .flat_map(|roll| roll.rolled_faces())
+            .collect() }
+
+    /// The totals of the determined rolls, combined using their `Unit`s' `Aggregation`s like the
+    /// dice of a pool
+    fn totals(&self) -> Values {
+        let mut out = Values::new();
+        for roll in self.rolls.iter() {
+            out.aggregate_all_values(roll.totals()); }
+        out }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Aggregation, Name, Unit, Value, Values,
+        dice::{Die, Face},
+        premade::number,
+        random::with_seed,
+        rollers::{DynamicDieRoller, DynamicPoolRoller, Roller, SubRoller},
+        units::BasicUnit };
+
+    #[test]
+    fn pool_size_comes_from_a_roll() {
+        let (unit, dice) = number::build();
+        let sut = DynamicPoolRoller::new(dice[2].clone(), unit.clone(), dice[3].clone());
+        assert_eq!(sut.description(), "(d4)d6");
+
+        let roll = sut.clone().inner_roll_with(with_seed(5));
+        assert_eq!(roll.intermediate_results(), "d4:[3] => d6:[6] + d6:[3] + d6:[1]");
+        assert_eq!(roll.rolled_faces().len(), 3);
+
+        // a count of 300 is more than a u8 can hold
+        let count = dice[0].clone().plus_modifier(Values::from(Value::new(&unit, 298)));
+        let big = DynamicPoolRoller::new(count, unit.clone(), dice[0].clone());
+        let count = big.inner_roll_with(with_seed(5)).rolled_faces().len();
+        assert!(count == 299 || count == 300, "rolled {} dice", count); }
+
+    #[test]
+    fn pools_follow_aggregation() {
+        let (unit, dice) = number::build();
+        let highest: Rc<dyn Unit> = BasicUnit::new(Name::new("Highest").unwrap(), "{}", false)
+            .aggregated_by(Aggregation::Max);
+        let faces = (1..=6)
+            .map(|num| Face::with_one_val(Name::from_num(num), Value::new(&highest, num as i32)))
+            .collect();
+        let d6 = Die::new(Name::new("d6").unwrap(), faces);
+        let sut = DynamicPoolRoller::new(dice[2].clone(), unit, d6);
+        for seed in 0..20 {
+            let roll = sut.clone().inner_roll_with(with_seed(seed));
+            let max = roll.rolled_faces().iter()
+                .map(|die_roll| die_roll.face().value_for(&highest).unwrap())
+                .max();
+            assert_eq!(roll.totals().value_for(&highest), max); } }
+
+    #[test]
+    fn die_size_comes_from_a_roll() {
+        let (unit, dice) = number::build();
+        let size = dice[3].clone().plus_modifier(Values::from(Value::new(&unit, 4)));
+        let sut: Rc<dyn SubRoller> = DynamicDieRoller::new(size, unit.clone(), unit.clone());
+        assert_eq!(sut.description(), "d(d6 + (4))");
+        for seed in 0..20 {
+            let roll = sut.clone().inner_roll_with(with_seed(seed));
+            let total = roll.totals().total_for(&unit);
+            assert!((1..=10).contains(&total)); }
+
+        // only the rolled face is made, so even the biggest die is cheap to roll
+        let huge = dice[0].clone().plus_modifier(Values::from(Value::new(&unit, i32::MAX - 2)));
+        let sut = DynamicDieRoller::new(huge, unit.clone(), unit.clone());
+        let total = sut.inner_roll_with(with_seed(5)).totals().total_for(&unit);
+        assert!(total >= 1); }
+
+    #[test]
+    fn pool_size_is_capped() {
+        let (unit, dice) = number::build();
+        let count = dice[0].clone().plus_modifier(Values::from(Value::new(&unit, i32::MAX - 2)));
+        let sut = DynamicPoolRoller::new(count, unit, dice[0].clone());
+        let rolled = sut.inner_roll_with(with_seed(5)).rolled_faces().len();
+        assert_eq!(rolled, DynamicPoolRoller::MAX_COUNT as usize); }
+}
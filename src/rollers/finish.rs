@@ -0,0 +1,77 @@
+use crate::rollers::{Roll, RollWalker, SubRoll};
+
+
+/// `FinishedRoll` wraps the outermost `SubRoll` of a roll, which is where its totals are finished
+/// off by clamping them to their `Unit`s' `Bounds`. Whatever was clamped is noted at the end of
+/// `intermediate_results()`, such as "Hit - Armor = 0 (floored from -2)", so nothing's hidden from the
+/// players. If nothing was clamped, it's exactly the same as the wrapped roll.
+///
+/// Every `Roller` that's also a `SubRoller` returns one of these from `roll_with()`, wrapped around
+/// what `inner_roll_with()` gives.
+pub struct FinishedRoll {
+    inner: Box<dyn SubRoll>
+}
+impl FinishedRoll {
+    pub fn new(inner: Box<dyn SubRoll>) -> Box<Self> {
+        Box::new(Self { inner }) }
+
+    /// Describes each total that the `Bounds` changed, e.g. "0 (floored from -2)"
+    fn clamp_notes(&self) -> Vec<String> {
+        self.inner.totals().into_iter()
+            .filter_map(|value| {
+                let clamped = value.unit.bounds().clamp(value.value);
+                if clamped == value.value { return None }
+                let how = if clamped > value.value { "floored" } else { "capped" };
+                Some(format!("{} ({} from {})", value.unit.output_for(clamped), how, value.value)) })
+            .collect() }
+}
+impl Roll for FinishedRoll {
+    fn intermediate_results(&self) -> String {
+        let notes = self.clamp_notes();
+        if notes.is_empty() { self.inner.intermediate_results() }
+        else { format!("{} = {}", self.inner.intermediate_results(), notes.join(", ")) } }
+
+    fn final_result(&self) -> String {
+        if self.clamp_notes().is_empty() { self.inner.final_result() }
+        else { self.inner.final_totals().to_string() } }
+
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) { self.inner.walk(walker) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit, Value, Values,
+        random::with_seed,
+        rollers::{ModifierRoller, Roller, SubRoller},
+        units::BasicUnit };
+
+    fn values(unit: &Rc<dyn Unit>, value: i32) -> Values {
+        Values::from(Value { unit: unit.clone(), value }) }
+
+    fn name(name: &str) -> Name { Name::new(name).unwrap() }
+
+    #[test]
+    fn clamps_only_the_final_totals() {
+        let damage: Rc<dyn Unit> = BasicUnit::new(name("Damage"), "{}", false).floored_at(0);
+        let floored = ModifierRoller::named(name("Hit"), values(&damage, 7))
+            .minus_named_modifier(name("Armor"), values(&damage, 9))
+            .roll_with(with_seed(1));
+        assert_eq!(floored.intermediate_results(), "Hit - Armor = 0 (floored from -2)");
+        assert_eq!(floored.final_result(), "0");
+
+        let unclamped = ModifierRoller::named(name("Hit"), values(&damage, 7))
+            .minus_named_modifier(name("Armor"), values(&damage, 9))
+            .plus_named_modifier(name("Bonus"), values(&damage, 5))
+            .roll_with(with_seed(1));
+        assert_eq!(unclamped.intermediate_results(), "(Hit - Armor) + Bonus");
+        assert_eq!(unclamped.final_result(), "3"); }
+
+    #[test]
+    fn caps_totals() {
+        let triumph: Rc<dyn Unit> = BasicUnit::new(name("Triumph"), "{} Triumph", false).capped_at(3);
+        let sut = ModifierRoller::named(name("Destiny"), values(&triumph, 5)).roll_with(with_seed(1));
+        assert_eq!(sut.intermediate_results(), "Destiny = 3 Triumph (capped from 5)");
+        assert_eq!(sut.final_result(), "3 Triumph"); }
+}
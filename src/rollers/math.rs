@@ -5,35 +5,43 @@ use std::{
 use crate::{
     Name, Values,
     random::Rng, 
-    rollers::{Roller, Roll, SubRoller, SubRoll, DieRoll} };
+    rollers::{Roller, Roll, SubRoller, SubRoll, DieRoll, DieStatus, RollWalker, finish::FinishedRoll} };
 
 
 /// When it comes to having a long stream of numbers being added and subtracted together, especially
 /// when you want to represent that equation again later in text, you need to know whether you're
 /// displaying the first value, an addition, or a subtraction. If you don't distinguish between them,
 /// you end up with clunky representations that differ from the original intent. This type
-/// encapsulates the three main representations in order to display correctly.
+/// encapsulates the three main representations in order to display correctly. Modifiers are shown
+/// the same way as additions, but they're kept apart since they're always added normally, rather
+/// than using their `Unit`s' `Aggregation`s.
 enum RollerMathType {
     First(Rc<dyn SubRoller>),
     Add(Rc<dyn SubRoller>),
+    Modifier(Rc<dyn SubRoller>),
     Subtract(Rc<dyn SubRoller>)
 }
 impl RollerMathType {
     fn description(&self) -> String {
         match self {
             RollerMathType::First(roller) => roller.inner_description(),
-            RollerMathType::Add(roller) => format!(" + {}", roller.inner_description()),
+            RollerMathType::Add(roller)
+            | RollerMathType::Modifier(roller) => format!(" + {}", roller.inner_description()),
             RollerMathType::Subtract(roller) => format!(" - {}", roller.inner_description()) } }
     
     fn roll_with(&self, rng: Rng) -> RollMathType {
         match self {
             RollerMathType::First(roller) => RollMathType::First(roller.clone().inner_roll_with(rng)),
             RollerMathType::Add(roller) => RollMathType::Add(roller.clone().inner_roll_with(rng)),
+            RollerMathType::Modifier(roller) => RollMathType::Modifier(roller.clone().inner_roll_with(rng)),
             RollerMathType::Subtract(roller) => RollMathType::Subtract(roller.clone().inner_roll_with(rng)) } }
 }
 
 
-/// A `Roller` that encapsulates a series of additions and subtractions of other `Roller`s.
+/// A `Roller` that encapsulates a series of additions and subtractions of other `Roller`s. The
+/// rollers that are added are combined using their `Unit`s' `Aggregation`s, in order, so a `Max`
+/// unit keeps the highest of them. Modifiers and subtracted rollers are always added and subtracted
+/// normally, so "highest die + 1" works the way you'd expect.
 pub struct MathRoller {
     inner: Vec<RollerMathType>
 }
@@ -45,6 +53,10 @@ impl MathRoller {
     /// Creates a new `MathRoller`, subtracting the results of the 2nd given roller from the first
     pub fn subtract(lhs: Rc<dyn SubRoller>, rhs: Rc<dyn SubRoller>) -> Rc<Self> {
         Rc::new(Self{ inner: vec![RollerMathType::First(lhs), RollerMathType::Subtract(rhs)] }) }
+
+    /// Creates a new `MathRoller`, adding the given modifier to the results of the given roller
+    pub(crate) fn add_modifier(lhs: Rc<dyn SubRoller>, modifier: Rc<dyn SubRoller>) -> Rc<Self> {
+        Rc::new(Self{ inner: vec![RollerMathType::First(lhs), RollerMathType::Modifier(modifier)] }) }
     
     /// Adds the given roller's results to the results of the rest of this roller
     pub fn plus(mut self, roller: Rc<dyn SubRoller>) -> Self {
@@ -53,13 +65,13 @@ impl MathRoller {
     
     /// Adds the given modifier to this roller's results
     pub fn plus_modifier(mut self, modifier: Values) -> Self {
-        self.inner.push(RollerMathType::Add(modifier.to_roller()));
+        self.inner.push(RollerMathType::Modifier(modifier.to_roller()));
         self }
     
     /// Adds the given modifier to this roller's results, using the given name as a display value
     /// for the description and intermediate results
     pub fn plus_named_modifier(mut self, modifier_name: Name, modifier: Values) -> Self {
-        self.inner.push(RollerMathType::Add(modifier.to_roller_with_name(modifier_name)));
+        self.inner.push(RollerMathType::Modifier(modifier.to_roller_with_name(modifier_name)));
         self }
     
     /// Adds all of the given rollers to the results of the rest of this roller
@@ -74,13 +86,13 @@ impl MathRoller {
     
     /// Subtracts the given modifier from this roller's results
     pub fn minus_modifier(mut self, modifier: Values) -> Self {
-        self.inner.push(RollerMathType::Add(modifier.to_roller()));
+        self.inner.push(RollerMathType::Subtract(modifier.to_roller()));
         self }
     
     /// Subtracts the given modifier from this roller's results, using the given name as a display
     /// value for the description and intermediate results
     pub fn minus_named_modifier(mut self, modifier_name: Name, modifier: Values) -> Self {
-        self.inner.push(RollerMathType::Add(modifier.to_roller_with_name(modifier_name)));
+        self.inner.push(RollerMathType::Subtract(modifier.to_roller_with_name(modifier_name)));
         self }
     
     /// Subtracts all of the given rollers from the results of the rest of this roller
@@ -96,7 +108,7 @@ impl Roller for MathRoller {
             .join("") }
     
     fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
-        MathRoll::new(self.inner.iter().map(|roller| roller.roll_with(rng.clone()))) }
+        FinishedRoll::new(self.inner_roll_with(rng)) }
 }
 impl SubRoller for MathRoller {
     fn is_simple(&self) -> bool { false }
@@ -110,26 +122,23 @@ impl SubRoller for MathRoller {
 enum RollMathType {
     First(Box<dyn SubRoll>),
     Add(Box<dyn SubRoll>),
+    Modifier(Box<dyn SubRoll>),
     Subtract(Box<dyn SubRoll>)
 }
 impl RollMathType {
     fn intermediate_results(&self) -> String {
         match self {
             RollMathType::First(roll) => roll.inner_intermediate_results(),
-            RollMathType::Add(roll) => format!(" + {}", roll.inner_intermediate_results()),
+            RollMathType::Add(roll)
+            | RollMathType::Modifier(roll) => format!(" + {}", roll.inner_intermediate_results()),
             RollMathType::Subtract(roll) => format!(" - {}", roll.inner_intermediate_results()) } }
     
     fn rolled_faces(&self) -> Vec<&DieRoll> {
         match self {
             RollMathType::First(roll) 
             | RollMathType::Add(roll) 
+            | RollMathType::Modifier(roll)
             | RollMathType::Subtract(roll) => roll.rolled_faces() } }
-    
-    fn totals(&self) -> Values {
-        match self {
-            RollMathType:: First(roll) 
-            | RollMathType::Add(roll) => roll.totals(),
-            RollMathType::Subtract(roll) => -roll.totals() } }
 }
 
 
@@ -148,6 +157,14 @@ impl Roll for MathRoll {
 
     fn final_result(&self) -> String {
         self.totals().to_string() }
+
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) {
+        for (idx, inner_roll) in self.inner.iter().enumerate() {
+            match inner_roll {
+                RollMathType::First(roll)
+                | RollMathType::Add(roll)
+                | RollMathType::Modifier(roll)
+                | RollMathType::Subtract(roll) => walker.part(idx, DieStatus::Kept, roll.as_ref()) } } }
 }
 impl SubRoll for MathRoll {
     fn is_simple(&self) -> bool { false }
@@ -159,7 +176,11 @@ impl SubRoll for MathRoll {
         output }
 
     fn totals(&self) -> Values {
-        self.inner.iter()
-            .map(RollMathType::totals)
-            .collect::<Values>() }
+        let mut totals = Values::new();
+        for inner_roll in self.inner.iter() {
+            match inner_roll {
+                RollMathType::First(roll) | RollMathType::Modifier(roll) => totals.add_all_values(roll.totals()),
+                RollMathType::Add(roll) => totals.aggregate_all_values(roll.totals()),
+                RollMathType::Subtract(roll) => totals.subtract_all_values(roll.totals()) } }
+        totals }
 }
\ No newline at end of file
@@ -24,18 +24,27 @@
 /// and 4 was added on after"; that kind of stuff, though more strictly defined than that),
 /// as well as calculating the end total(s).
 mod die;
+mod dynamic;
+mod finish;
 mod math;
 mod multi;
+mod natural;
 mod pool;
+mod sets;
 mod stats;
+mod tree;
 mod value;
 
 pub use self::{
     die::*,
+    dynamic::*,
     math::*,
     multi::*,
+    natural::*,
     pool::*,
+    sets::*,
     stats::*,
+    tree::*,
     value::* };
 
 use std::{
@@ -43,7 +52,7 @@ use std::{
     rc::Rc,
     vec::Vec };
 use crate::{
-    Name, Values,
+    Name, Unit, Values,
     random::{default_rng, Rng} };
 
 
@@ -96,7 +105,9 @@ pub trait Roller {
     /// Returns a `String` that describes what the roller rolls. i.e. "2d8 + 6"
     fn description(&self) -> String;
 
-    /// "Rolls" the dice using the given random number generator and produces a `Roll`
+    /// "Rolls" the dice using the given random number generator and produces a `Roll`. Rollers
+    /// that are also `SubRoller`s roll the same way as `inner_roll_with()`, then wrap the result in
+    /// a `FinishedRoll` to finish the totals off.
     fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll>;
 
     /// "Rolls" the dice using the default random number generator and produces a `Roll`
@@ -159,19 +170,22 @@ pub trait SubRoller: Roller where Self: 'static {
         MathRoller::subtract(self, other) }
 
     fn plus_modifier(self: Rc<Self>, value: Values) -> Rc<MathRoller> where Self: Sized {
-        MathRoller::add(self, value.to_roller()) }
+        MathRoller::add_modifier(self, value.to_roller()) }
 
     fn minus_modifier(self: Rc<Self>, value: Values) -> Rc<MathRoller> where Self: Sized {
         MathRoller::subtract(self, value.to_roller()) }
     
     fn plus_named_modifier(self: Rc<Self>, name: Name, values: Values) -> Rc<MathRoller> where Self: Sized {
-        MathRoller::add(self, values.to_roller_with_name(name)) }
+        MathRoller::add_modifier(self, values.to_roller_with_name(name)) }
         
     fn minus_named_modifier(self: Rc<Self>, name: Name, values: Values) -> Rc<MathRoller> where Self: Sized {
         MathRoller::subtract(self, values.to_roller_with_name(name)) }
 
     fn get_stats(self: Rc<Self>, num_runs: NonZero<u32>) -> Rc<StatsRoller> where Self: Sized {
         StatsRoller::new(self, num_runs) }
+
+    fn flagged(self: Rc<Self>, condition: FaceCondition, unit: Rc<dyn Unit>) -> Rc<FlaggedRoller> where Self: Sized {
+        FlaggedRoller::new(self).flag(condition, unit) }
 }
 
 
@@ -204,12 +218,37 @@ pub trait SubRoller: Roller where Self: 'static {
 ///
 /// Again, you may have an exceptional case (typically if it's not a `ComposableRoll`). But that's
 /// the general way to implement it.
+///
+/// ##Implementing `walk()`
+/// If there are any dice in your `Roll`, pass each wrapped `Roll` to the `RollWalker`'s `part()`
+/// method with the `DieStatus` it gets (`Kept`, if it counts like normal), so that `all_dice()` can
+/// find them. `Roll`s without dice, such as `StatisticsRoll`, can leave the default, which finds
+/// nothing.
 pub trait Roll {
     /// Returns a `String` that lays out all the dice rolls and how they were combined together
     fn intermediate_results(&self) -> String;
 
     /// Returns a `String` that summarizes the total of all the rolls and how they're combined together.
     fn final_result(&self) -> String;
+
+    /// Tells the `RollWalker` about each part of this roll (see `RollWalker::part()`), or about the
+    /// die itself if this is a `DieRoll`
+    fn walk<'a>(&'a self, _walker: &mut RollWalker<'a>) {}
+
+    /// Do not override. Returns every die rolled anywhere in this roll, including the ones that
+    /// were dropped or didn't count for other reasons, each with its `DieStatus` and path.
+    fn all_dice(&self) -> Vec<RolledDie<'_>> {
+        let mut walker = RollWalker::new();
+        self.walk(&mut walker);
+        walker.into_dice() }
+
+    /// Do not override. Returns `all_dice()` as a JSON array, with each die's presentation
+    /// details. See `RolledDie::to_json()`.
+    fn dice_to_json(&self) -> String {
+        let dice = self.all_dice().iter()
+            .map(RolledDie::to_json)
+            .collect::<Vec<String>>();
+        format!("[{}]", dice.join(",")) }
 }
 
 
@@ -232,7 +271,6 @@ pub trait Roll {
 /// a single Vector. It could be made with a default implementation, but that would require a new 
 /// method for you to implement that returns all the wrapped `Roll`s. That would be easier for you,
 /// but it would bloat the code with otherwise unused methods, and it's not that difficult to do now.
-/// Keep in mind, there's a chance this method is also going to be removed.
 ///
 /// ##Implementing `totals()`
 /// Using the `Values` objects from inner `Roll`s' `totals()` methods, combine them and add and 
@@ -250,9 +288,15 @@ pub trait SubRoll: Roll {
         if self.is_simple() { self.intermediate_results() }
         else { format!("({})", self.intermediate_results()) } }
 
-    /// Returns all the `DieRoll`s that make up this total `Roll`. 
+    /// Returns all the `DieRoll`s that make up this total `Roll`. Dropped dice aren't included;
+    /// use `all_dice()` to see every die that was rolled.
     fn rolled_faces(&self) -> Vec<&DieRoll>;
 
     /// Returns the final total of all the rolls combined.
     fn totals(&self) -> Values;
+
+    /// Do not override. This is `totals()` once the roll is finished, with each total clamped to
+    /// its `Unit`'s `Bounds`. Only use it for the outermost roll; inner rolls should use `totals()`.
+    fn final_totals(&self) -> Values { self.totals().clamped() }
+
 }
\ No newline at end of file
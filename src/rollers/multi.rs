@@ -2,7 +2,7 @@ use std::rc::Rc;
 use crate::{
     Name, 
     random::Rng,
-    rollers::{Roll, Roller} };
+    rollers::{DieStatus, Roll, RollWalker, Roller, SubRoller} };
 
 
 /// `NamedRoller` is designed specifically for `MultiRoller`, though there's no good reason you can't
@@ -13,20 +13,32 @@ use crate::{
 ///
 /// Note that, unlike on most rollers, the constructors of `NamedRoller` don't automatically wrap
 /// themselves in `Rc`. The `rc()` method allows for a nice, simple way to do so quickly.
+///
+/// If the roller is a `SubRoller`, create it with `new_sub()` instead of `new()`, so it keeps hold
+/// of the `SubRoller` for `MultiStatsRoller`, which needs the totals of each roll.
 #[derive(Clone)]
 pub struct NamedRoller {
   name: Name,
-  roller: Rc<dyn Roller>
+  roller: Rc<dyn Roller>,
+  sub_roller: Option<Rc<dyn SubRoller>>
 }
 impl NamedRoller {
     pub fn new(name: Name, roller: Rc<dyn Roller>) -> Self {
-        Self { name, roller } }
+        Self { name, roller, sub_roller: None } }
+
+    pub fn new_sub(name: Name, roller: Rc<dyn SubRoller>) -> Self {
+        Self { name, roller: roller.clone(), sub_roller: Some(roller) } }
     
     pub fn for_index(idx: usize, roller: Rc<dyn Roller>) -> Self {
-        Self { name: (idx + 1).into(), roller } }
+        Self { name: (idx + 1).into(), roller, sub_roller: None } }
         
     pub fn numbered(num: usize, roller: Rc<dyn Roller>) -> Self {
-        Self { name: num.into(), roller } }
+        Self { name: num.into(), roller, sub_roller: None } }
+
+    pub fn name(&self) -> &Name { &self.name }
+
+    /// The roller as a `SubRoller`, if it was created with `new_sub()`
+    pub fn sub_roller(&self) -> Option<&Rc<dyn SubRoller>> { self.sub_roller.as_ref() }
     
     pub fn rc(self) -> Rc<Self> {
         Rc::new(self) }
@@ -56,6 +68,8 @@ impl Roller for NamedRoller {
 /// 
 /// Using `new_numbered()`, you can also build one from a collection of other rollers, and they will simply all get 
 /// index-based names. 
+///
+/// To get the statistics of rolling them together, see `MultiStatsRoller::from_multi()`.
 pub struct MultiRoller {
     inner: Vec<NamedRoller>
 }
@@ -80,6 +94,14 @@ impl MultiRoller {
     pub fn add_numbered(&mut self, roller: Rc<dyn Roller>) -> &Self {
         self.inner.push(NamedRoller::for_index(self.inner.len(), roller));
         self }
+
+    /// Adds a `SubRoller` with a name, keeping it as a `SubRoller` (see `NamedRoller::new_sub()`)
+    pub fn add_sub_with_name(&mut self, name: Name, roller: Rc<dyn SubRoller>) -> &Self {
+        self.inner.push(NamedRoller::new_sub(name, roller));
+        self }
+
+    /// The named rollers, in the order they were added
+    pub fn rollers(&self) -> &[NamedRoller] { &self.inner }
 }
 impl Roller for MultiRoller {
     fn description(&self) -> String {
@@ -106,6 +128,8 @@ impl Roll for NamedRoll {
 
     fn final_result(&self) -> String {
         format!("{}: {}", self.name, self.roll.final_result()) }
+
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) { self.roll.walk(walker) }
 }
 
 
@@ -127,6 +151,11 @@ impl Roll for MultiRoll {
           .map(NamedRoll::final_result)
           .collect::<Vec<String>>()
           .join("\n") }
+
+    /// Each named roll is a part, in the order they were added
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) {
+        for (idx, roll) in self.inner.iter().enumerate() {
+            walker.part(idx, DieStatus::Kept, roll); } }
 }
 impl FromIterator<NamedRoll> for MultiRoll {
     fn from_iter<T: IntoIterator<Item=NamedRoll>>(iter: T) -> Self {
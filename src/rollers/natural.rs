@@ -0,0 +1,210 @@
+use std::rc::Rc;
+use crate::{
+    Unit, Value, Values,
+    random::Rng,
+    rollers::{DieRoll, GroupBy, Roll, RollWalker, Roller, SetAnalysis, SetPreference, SubRoll, SubRoller, finish::FinishedRoll} };
+
+
+/// Checks the rolled faces for `FaceCondition::Custom`
+pub type FaceCheck = Rc<dyn Fn(&[&DieRoll]) -> bool>;
+
+
+/// A check on the natural faces that were rolled, rather than the totals they add up to, such as
+/// a natural 20 on the d20 no matter what was added to it. It looks at the dice from a roll's
+/// `all_dice()` that count towards its totals, leaving out dice that a pool dropped as well as dice
+/// rolled because of an explosion, since those aren't natural rolls.
+#[derive(Clone)]
+pub enum FaceCondition {
+    /// A face with the given label came up (ignoring case)
+    Label(String),
+    /// A face worth exactly the given amount of the `Unit` came up
+    Value(Rc<dyn Unit>, i32),
+    /// Only looks at the faces rolled on dice with the given name (ignoring case), such as the d20
+    /// of "d20 + d4"
+    OnDie(String, Box<FaceCondition>),
+    /// At least the given number of faces match each other, such as 2 for doubles. See `GroupBy`.
+    Matching(u32, GroupBy),
+    /// Anything else, such as Mothership's doubles on a single d100 (11, 22, and so on)
+    Custom(FaceCheck),
+}
+impl FaceCondition {
+    pub fn label(label: &str) -> Self { FaceCondition::Label(label.to_string()) }
+
+    pub fn value(unit: &Rc<dyn Unit>, value: i32) -> Self { FaceCondition::Value(unit.clone(), value) }
+
+    pub fn doubles(group_by: GroupBy) -> Self { FaceCondition::Matching(2, group_by) }
+
+    pub fn triples(group_by: GroupBy) -> Self { FaceCondition::Matching(3, group_by) }
+
+    pub fn custom(check: impl Fn(&[&DieRoll]) -> bool + 'static) -> Self {
+        FaceCondition::Custom(Rc::new(check)) }
+
+    /// Narrows the condition down to only the faces rolled on dice with the given name
+    pub fn on_die(self, die_name: &str) -> Self {
+        FaceCondition::OnDie(die_name.to_string(), Box::new(self)) }
+
+    /// Checks whether the rolled faces meet this condition
+    pub fn is_met_by(&self, faces: &[&DieRoll]) -> bool {
+        match self {
+            FaceCondition::Label(label) =>
+                faces.iter().any(|roll| roll.face().label.matches(label)),
+            FaceCondition::Value(unit, value) =>
+                faces.iter().any(|roll| roll.face().value_for(unit) == Some(*value)),
+            FaceCondition::OnDie(die_name, condition) => {
+                let on_die: Vec<&DieRoll> = faces.iter()
+                    .filter(|roll| roll.die().name.matches(die_name))
+                    .copied()
+                    .collect();
+                condition.is_met_by(&on_die) }
+            FaceCondition::Matching(width, group_by) =>
+                SetAnalysis::from_faces(faces.iter().map(|roll| roll.face().as_ref()), group_by, &SetPreference::Widest)
+                    .best()
+                    .is_some_and(|set| set.width >= *width),
+            FaceCondition::Custom(check) => check(faces) } }
+}
+
+
+/// `FlaggedRoller` rolls another `SubRoller` and adds a flag to its totals for each of its
+/// `FaceCondition`s that the rolled faces meet, such as a "Critical" for a natural 20. The flag is
+/// a 1 for the `Unit` it was given, so it shows up in the output and can be counted in stats like
+/// any other total.
+#[derive(Clone)]
+pub struct FlaggedRoller {
+    inner: Rc<dyn SubRoller>,
+    flags: Vec<(FaceCondition, Rc<dyn Unit>)>,
+}
+impl FlaggedRoller {
+    /// Creates a `FlaggedRoller` without any flags yet
+    pub fn new(inner: Rc<dyn SubRoller>) -> Rc<Self> {
+        Rc::new(Self { inner, flags: Vec::new() }) }
+
+    /// Adds a flag, which puts a 1 for the `Unit` in the totals when the condition is met
+    pub fn flag(self: Rc<Self>, condition: FaceCondition, unit: Rc<dyn Unit>) -> Rc<Self> {
+        let mut flagged = Rc::unwrap_or_clone(self);
+        flagged.flags.push((condition, unit));
+        Rc::new(flagged) }
+}
+impl Roller for FlaggedRoller {
+    /// Flags don't change what's rolled, so this is the inner roller's description
+    fn description(&self) -> String { self.inner.description() }
+
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        FinishedRoll::new(self.inner_roll_with(rng)) }
+}
+impl SubRoller for FlaggedRoller {
+    fn is_simple(&self) -> bool { self.inner.is_simple() }
+
+    fn inner_roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn SubRoll> {
+        let inner = self.inner.clone().inner_roll_with(rng);
+        let raised = {
+            let faces = natural_faces(inner.as_ref());
+            self.flags.iter()
+                .filter(|(condition, _)| condition.is_met_by(&faces))
+                .map(|(_, unit)| unit.clone())
+                .collect() };
+        Box::new(FlaggedRoll { inner, raised }) }
+}
+
+
+/// The faces of the dice that count towards the roll's totals, other than the ones rolled because
+/// of an explosion. See `FaceCondition`.
+fn natural_faces(roll: &dyn SubRoll) -> Vec<&DieRoll> {
+    roll.all_dice().into_iter()
+        .filter(|die| die.status.counts() && !die.from_explosion)
+        .map(|die| die.roll)
+        .collect() }
+
+
+/// The `Roll` of a `FlaggedRoller`, holding the `Unit`s of the flags that were raised
+struct FlaggedRoll {
+    inner: Box<dyn SubRoll>,
+    raised: Vec<Rc<dyn Unit>>,
+}
+impl Roll for FlaggedRoll {
+    /// Shows the inner roll, followed by the raised flags, such as "d20:[20] + (5) => Critical". With
+    /// any flags raised, it isn't simple, so the whole thing gets wrapped when it's part of
+    /// something bigger.
+    fn intermediate_results(&self) -> String {
+        let inner = self.inner.intermediate_results();
+        if self.raised.is_empty() { return inner }
+        let flags = self.raised.iter()
+            .map(|unit| unit.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("{} => {}", inner, flags) }
+
+    fn final_result(&self) -> String { self.totals().to_string() }
+
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) { self.inner.walk(walker) }
+}
+impl SubRoll for FlaggedRoll {
+    fn is_simple(&self) -> bool { self.raised.is_empty() && self.inner.is_simple() }
+
+    fn rolled_faces(&self) -> Vec<&DieRoll> { self.inner.rolled_faces() }
+
+    fn totals(&self) -> Values {
+        let mut out = self.inner.totals();
+        for unit in self.raised.iter() {
+            out.add_value(Value::new(unit, 1)); }
+        out }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit, Value, Values,
+        dice::{Die, Face},
+        premade::number,
+        random::with_seed,
+        rollers::{FaceCondition, GroupBy, SubRoller},
+        units::BasicUnit };
+
+    fn flag(name: &str) -> Rc<dyn Unit> {
+        BasicUnit::new(Name::new(name).unwrap(), name, true) }
+
+    #[test]
+    fn crits_use_the_natural_face() {
+        let (unit, dice) = number::build();
+        let critical = flag("Critical");
+        let sut = dice[7].clone()
+            .plus_modifier(Values::from(Value::new(&unit, 5)))
+            .flagged(FaceCondition::value(&unit, 20).on_die("d20"), critical.clone());
+        for seed in 0..200 {
+            let roll = sut.clone().inner_roll_with(with_seed(seed));
+            let natural = roll.rolled_faces()[0].face().value_for(&unit).unwrap();
+            assert_eq!(roll.totals().value_for(&critical).is_some(), natural == 20);
+            assert_eq!(roll.intermediate_results().ends_with("=> Critical"), natural == 20);
+            if natural == 20 {
+                assert_eq!(roll.intermediate_results(), "d20:[20] + (5) => Critical"); } } }
+
+    #[test]
+    fn explosions_are_not_natural() {
+        let (unit, _) = number::build();
+        let explodes: Rc<dyn Unit> = BasicUnit::new(Name::new("Explodes").unwrap(), "", true);
+        let faces = vec![
+            Face::with_one_val(Name::from_num(1), Value::new(&unit, 1)),
+            Face::with_two_vals(Name::from_num(2), Value::new(&unit, 2), Value::new(&explodes, 1))];
+        let coin = Rc::new(Rc::unwrap_or_clone(Die::new(Name::new("Coin").unwrap(), faces)).exploding_on(explodes));
+        let twos = flag("Twos");
+        let sut = coin.flagged(FaceCondition::Matching(2, GroupBy::Label), twos.clone());
+        // every die after the first is from an explosion, so there's only ever one natural face
+        let mut exploded = 0;
+        for seed in 0..50 {
+            let roll = sut.clone().inner_roll_with(with_seed(seed));
+            exploded += (roll.rolled_faces().len() > 2) as u32;
+            assert_eq!(roll.totals().value_for(&twos), None); }
+        assert!(exploded > 0); }
+
+    #[test]
+    fn doubles_across_a_pool() {
+        let (unit, dice) = number::build();
+        let doubles = flag("Doubles");
+        let sut = dice[3].clone().n_times(2).flagged(FaceCondition::doubles(GroupBy::Label), doubles.clone());
+        for seed in 0..50 {
+            let roll = sut.clone().inner_roll_with(with_seed(seed));
+            let faces = roll.rolled_faces();
+            let matched = faces[0].face().value_for(&unit) == faces[1].face().value_for(&unit);
+            assert_eq!(roll.totals().value_for(&doubles), matched.then_some(1)); } }
+}
@@ -4,7 +4,7 @@ use std::{
 };
 use crate::{
     {Unit, Values},
-    rollers::{DieRoll, Roll, Roller, SubRoll, SubRoller},
+    rollers::{DieRoll, DieStatus, Roll, RollWalker, Roller, SubRoll, SubRoller, finish::FinishedRoll},
     random::Rng
 };
 use self::Strategy::*;
@@ -12,63 +12,121 @@ use self::Strategy::*;
 /// The `PoolRoller` uses a `Strategy`  in order to implement the  "drop lowest"
 /// and "drop highest" options.  There's also the default "keep all" option. For
 /// the options that drop dice, it needs a number of how many to drop, along with 
-/// a list of `Relationship`s by which to sort the rolls in order to determine 
-/// which are the lowest or highest. 
+/// a list of `Unit`s by which to sort the rolls in order to determine which are
+/// the lowest or highest. If the rolls need to be sorted some other way, such as
+/// by a score or a custom comparator, use `DropLowestBy` or `DropHighestBy` with
+/// a `RollOrder` instead.
 pub enum Strategy {
     DropLowest{ count: u8, order_by: Vec<Rc<dyn Unit>> },
     DropHighest{ count: u8, order_by: Vec<Rc<dyn Unit>> },
+    DropLowestBy{ count: u8, order: RollOrder },
+    DropHighestBy{ count: u8, order: RollOrder },
     KeepAll
 }
 impl Strategy {
+    /// Drops the lowest `count` rolls, ordered by the totals of the `Unit`s in turn
+    pub fn drop_lowest(count: u8, order_by: Vec<Rc<dyn Unit>>) -> Self {
+        DropLowest { count, order_by } }
+
+    /// Drops the highest `count` rolls, ordered by the totals of the `Unit`s in turn
+    pub fn drop_highest(count: u8, order_by: Vec<Rc<dyn Unit>>) -> Self {
+        DropHighest { count, order_by } }
+
+    /// Drops the lowest `count` rolls, ordered by the given `RollOrder`
+    pub fn drop_lowest_by(count: u8, order: impl Into<RollOrder>) -> Self {
+        DropLowestBy { count, order: order.into() } }
+
+    /// Drops the highest `count` rolls, ordered by the given `RollOrder`
+    pub fn drop_highest_by(count: u8, order: impl Into<RollOrder>) -> Self {
+        DropHighestBy { count, order: order.into() } }
+
     pub fn count(&self) -> u8 {
         match self {
-            DropLowest{count, order_by: _} => { *count },
-            DropHighest{count, order_by: _} => { *count },
+            DropLowest{count, order_by: _}
+            | DropLowestBy{count, order: _} => { *count },
+            DropHighest{count, order_by: _}
+            | DropHighestBy{count, order: _} => { *count },
             KeepAll => { 0 } } }
     
     pub fn is_simple(&self) -> bool {
-        match self { 
-            KeepAll => true,
-            _ => false } }
+        matches!(self, KeepAll) }
     
     /// Uses the order_by field in this instance to act as a comparator for sorting Rolls.
-    /// If a roll doesn't have a Value for one of the Relationships, it uses a default value of 0.
-    /// This seems likes the most often correct behavior for sorting Rolls. The other obvious 
-    /// behavior that might come up is to default to the MIN value. This may be changed if it
-    /// turns out that this is the only actual expected behavior. Otherwise, if it turns out that
-    /// we need to support both options, we'll need a field in Relationship to hold the default 
-    /// value, either as an i32 (most flexible) or enum with the options of Zero and Min (more 
-    /// compact, maybe).
-    fn order_comparator(&self, roll1: &Box<dyn SubRoll>, roll2: &Box<dyn SubRoll>) -> Ordering {
+    fn order_comparator(&self, roll1: &dyn SubRoll, roll2: &dyn SubRoll) -> Ordering {
         match self {
             KeepAll => { Ordering::Equal }
-            DropLowest {count: _, order_by: order}
-            | DropHighest {count: _, order_by: order} => {
-                let roll1_vals = roll1.totals();
-                let roll2_vals = roll2.totals();
-                for unit in order {
-                    // Treat an absence of a Value for a Relationship as a 0 for it. 
-                    // See function doc for more details
-                    let r1val = roll1_vals.value_for(&unit).unwrap_or(0);
-                    let r2val = roll2_vals.value_for(&unit).unwrap_or(0);
-                    if r1val == r2val { continue; }
-                    else { return Ord::cmp(&r1val, &r2val) }
-                }
-                Ordering::Equal } } }
+            DropLowest {count: _, order_by: units}
+            | DropHighest {count: _, order_by: units} => RollOrder::compare_units(units, roll1, roll2),
+            DropLowestBy {count: _, order}
+            | DropHighestBy {count: _, order} => order.compare(roll1, roll2) } }
 
     /// needed for the `PoolRoll` to describe itself properly
     fn descriptor(&self) -> String {
         match self {
             KeepAll => String::new(), 
-            DropLowest{ count, order_by: _} => 
+            DropLowest{ count, order_by: _}
+            | DropLowestBy{ count, order: _ } => 
                 if *count == 1 { String::from(" drop lowest") }
                 else { format!(" drop lowest {}", count) },
-            DropHighest{ count, order_by: _} => 
+            DropHighest{ count, order_by: _}
+            | DropHighestBy{ count, order: _ } => 
                 if *count == 1 { String::from(" drop highest") }
                 else { format!(" drop highest {}", count) } } } 
 }
 
 
+/// Scores a roll for `RollOrder::Score`
+pub type RollScorer = Rc<dyn Fn(&dyn SubRoll) -> i32>;
+
+/// Compares two rolls for `RollOrder::Custom`
+pub type RollComparator = Rc<dyn Fn(&dyn SubRoll, &dyn SubRoll) -> Ordering>;
+
+
+/// How a `Strategy::DropLowestBy` or `Strategy::DropHighestBy` decides which rolls are the lowest
+/// and highest
+#[derive(Clone)]
+pub enum RollOrder {
+    /// Compares the totals of each `Unit` in turn, moving on to the next one only if they're tied.
+    /// If a roll doesn't have a Value for one of the Units, it uses the Unit's default value (see
+    /// `Unit::default_value()`), which is 0 unless the Unit says otherwise.
+    Units(Vec<Rc<dyn Unit>>),
+    /// Gives each roll a score, and lower scores are lower rolls. Good for things like ranking the
+    /// faces by their labels.
+    Score(RollScorer),
+    /// Compares two rolls directly, for when a score won't do, such as "fewest net successes,
+    /// with ties broken by the most threat"
+    Custom(RollComparator),
+}
+impl RollOrder {
+    pub fn by_score(score: impl Fn(&dyn SubRoll) -> i32 + 'static) -> Self {
+        RollOrder::Score(Rc::new(score)) }
+
+    pub fn custom(compare: impl Fn(&dyn SubRoll, &dyn SubRoll) -> Ordering + 'static) -> Self {
+        RollOrder::Custom(Rc::new(compare)) }
+
+    pub fn compare(&self, roll1: &dyn SubRoll, roll2: &dyn SubRoll) -> Ordering {
+        match self {
+            RollOrder::Units(units) => Self::compare_units(units, roll1, roll2),
+            RollOrder::Score(score) => score(roll1).cmp(&score(roll2)),
+            RollOrder::Custom(compare) => compare(roll1, roll2) } }
+
+    /// See `RollOrder::Units`
+    fn compare_units(units: &[Rc<dyn Unit>], roll1: &dyn SubRoll, roll2: &dyn SubRoll) -> Ordering {
+        let roll1_vals = roll1.totals();
+        let roll2_vals = roll2.totals();
+        for unit in units {
+            let r1val = roll1_vals.total_for(unit);
+            let r2val = roll2_vals.total_for(unit);
+            if r1val == r2val { continue; }
+            else { return Ord::cmp(&r1val, &r2val) }
+        }
+        Ordering::Equal }
+}
+impl From<Vec<Rc<dyn Unit>>> for RollOrder {
+    fn from(units: Vec<Rc<dyn Unit>>) -> Self { RollOrder::Units(units) }
+}
+
+
 /// `PoolRoller` is a `Roller` that is used to do any rolling that involves 
 /// multiple of the same roll or dice that will be added up, along with the 
 /// option to drop some of those rolls based on which rolls are the lowest 
@@ -122,29 +180,7 @@ impl Roller for PoolRoller {
             format!("{}({}){}", self.count, self.die.description(), self.strategy.descriptor()) } }
 
     fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
-        let mut rolls: Vec<Box<dyn SubRoll>> = Vec::with_capacity(self.count as usize);
-        for _ in 0..self.count {
-            rolls.push(self.die.clone().inner_roll_with(rng.clone()));
-        }
-        match self.strategy {
-            KeepAll => {
-                PoolRoll::new(
-                    rolls,
-                    Vec::with_capacity(0) ) }
-            DropLowest { count, order_by: _ } => {
-                let cut_idx = count as usize;
-                rolls.sort_by(|a, b| self.strategy.order_comparator(a, b));
-                let kept = rolls.split_off(cut_idx);
-                PoolRoll::new(
-                    kept,
-                    rolls ) }
-            DropHighest { count, order_by: _ } => {
-                let cut_idx = rolls.len() - (count as usize);
-                rolls.sort_by(|a, b| self.strategy.order_comparator(a, b));
-                let dropped = rolls.split_off(cut_idx);
-                PoolRoll::new(
-                    rolls,
-                    dropped ) } } }
+        FinishedRoll::new(self.inner_roll_with(rng)) }
 }
 impl SubRoller for PoolRoller {
     fn is_simple(&self) -> bool { self.die.is_simple() && self.strategy.is_simple() }
@@ -159,16 +195,18 @@ impl SubRoller for PoolRoller {
                 PoolRoll::new(
                     rolls,
                     Vec::with_capacity(0) ) }
-            DropLowest { count, order_by: _ } => {
+            DropLowest { count, order_by: _ }
+            | DropLowestBy { count, order: _ } => {
                 let cut_idx = count as usize;
-                rolls.sort_by(|a, b| self.strategy.order_comparator(a, b));
+                rolls.sort_by(|a, b| self.strategy.order_comparator(a.as_ref(), b.as_ref()));
                 let kept = rolls.split_off(cut_idx);
                 PoolRoll::new(
                     kept,
                     rolls ) }
-            DropHighest { count, order_by: _ } => {
+            DropHighest { count, order_by: _ }
+            | DropHighestBy { count, order: _ } => {
                 let cut_idx = rolls.len() - (count as usize);
-                rolls.sort_by(|a, b| self.strategy.order_comparator(a, b));
+                rolls.sort_by(|a, b| self.strategy.order_comparator(a.as_ref(), b.as_ref()));
                 let dropped = rolls.split_off(cut_idx);
                 PoolRoll::new(
                     rolls,
@@ -201,9 +239,16 @@ impl Roll for PoolRoll {
                 Self::build_intermediate_results_part(&self.dropped_rolls, ", ") ) } }
     
     fn final_result(&self) -> String { self.totals().to_string() }
+
+    /// The kept rolls come first, followed by the dropped ones
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) {
+        for (idx, roll) in self.kept_rolls.iter().enumerate() {
+            walker.part(idx, DieStatus::Kept, roll.as_ref()); }
+        for (idx, roll) in self.dropped_rolls.iter().enumerate() {
+            walker.part(self.kept_rolls.len() + idx, DieStatus::Dropped, roll.as_ref()); } }
 }
 impl SubRoll for PoolRoll {
-    fn is_simple(&self) -> bool { self.dropped_rolls.len() == 0 }
+    fn is_simple(&self) -> bool { self.dropped_rolls.is_empty() }
 
     /// Returns the rolled faces of just the kept rolls
     fn rolled_faces(&self) -> Vec<&DieRoll> {
@@ -212,10 +257,77 @@ impl SubRoll for PoolRoll {
             out.append(roll.rolled_faces().as_mut()) }
         out }
 
-    /// Returns the totals of just the kept rolls
+    /// Returns the totals of just the kept rolls, combined using their `Unit`s' `Aggregation`s
     fn totals(&self) -> Values {
         let mut out = Values::new();
         for roll in self.kept_rolls.iter() {
-            out.add_all_values(roll.totals()); }
+            out.aggregate_all_values(roll.totals()); }
         out }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Ordering, rc::Rc};
+    use crate::{
+        Name, Unit, Value,
+        dice::{Die, Face},
+        random::with_seed,
+        rollers::{DieRoll, RollOrder, Strategy, SubRoll, SubRoller},
+        units::BasicUnit };
+
+    fn name(name: &str) -> Name { Name::new(name).unwrap() }
+
+    #[test]
+    fn orders_by_custom_comparators_and_scores() {
+        let success: Rc<dyn Unit> = BasicUnit::new(name("Success"), "{} Success", true);
+        let threat: Rc<dyn Unit> = BasicUnit::new(name("Threat"), "{} Threat", true);
+        let faces = vec![
+            Face::with_one_val(name("S"), Value { unit: success.clone(), value: 1 }),
+            Face::with_two_vals(name("ST"), Value { unit: success.clone(), value: 1 }, Value { unit: threat.clone(), value: 1 })];
+        let die = Die::new(name("Genesys"), faces.clone());
+        let (plain, threatening) = (DieRoll::new(die.clone(), faces[0].clone()), DieRoll::new(die, faces[1].clone()));
+
+        let by_success: RollOrder = vec![success.clone()].into();
+        assert_eq!(by_success.compare(plain.as_ref(), threatening.as_ref()), Ordering::Equal);
+        let drop_lowest = Strategy::drop_lowest(2, vec![success.clone()]);
+        assert_eq!((drop_lowest.count(), drop_lowest.descriptor()), (2, " drop lowest 2".to_string()));
+        assert_eq!(Strategy::drop_highest(1, vec![success.clone()]).descriptor(), " drop highest");
+
+        // fewest net successes, then most threat
+        let genesys = RollOrder::custom(move |a, b| a.totals().total_for(&success)
+            .cmp(&b.totals().total_for(&success))
+            .then(b.totals().total_for(&threat).cmp(&a.totals().total_for(&threat))));
+        assert_eq!(genesys.compare(plain.as_ref(), threatening.as_ref()), Ordering::Greater);
+
+        let ranks = ["ST", "S"];
+        let by_label = RollOrder::by_score(move |roll: &dyn SubRoll| roll.rolled_faces().iter()
+            .map(|face| ranks.iter().position(|rank| face.face().label.matches(rank)).unwrap() as i32)
+            .sum());
+        assert_eq!(by_label.compare(plain.as_ref(), threatening.as_ref()), Ordering::Greater); }
+
+    fn pips(roll: &dyn SubRoll, unit: &Rc<dyn Unit>) -> Vec<i32> {
+        roll.rolled_faces().iter()
+            .map(|face| face.totals().total_for(unit))
+            .collect() }
+
+    #[test]
+    fn drops_rolls_by_score_and_custom_order() {
+        let unit: Rc<dyn Unit> = BasicUnit::new(name("Pips"), "{}", false);
+        let faces = (1..=6)
+            .map(|num| Face::with_one_val(Name::from_num(num as usize), Value { unit: unit.clone(), value: num }))
+            .collect();
+        let die = Die::new(name("d6"), faces);
+        // both orders rank the dice backwards, so the lowest pips are the "highest" rolls
+        let backwards = unit.clone();
+        let by_score = Strategy::drop_lowest_by(3, RollOrder::by_score(move |roll: &dyn SubRoll| -roll.totals().total_for(&backwards)));
+        let backwards = unit.clone();
+        let by_comparator = Strategy::drop_highest_by(3, RollOrder::custom(move |a, b| b.totals().total_for(&backwards)
+            .cmp(&a.totals().total_for(&backwards))));
+        let all = die.clone().n_times(4);
+        let fewest = die.clone().n_times_and(4, by_score).unwrap();
+        let most = die.n_times_and(4, by_comparator).unwrap();
+        for seed in 0..20 {
+            let rolled = pips(all.clone().inner_roll_with(with_seed(seed)).as_ref(), &unit);
+            assert_eq!(pips(fewest.clone().inner_roll_with(with_seed(seed)).as_ref(), &unit), vec![*rolled.iter().min().unwrap()]);
+            assert_eq!(pips(most.clone().inner_roll_with(with_seed(seed)).as_ref(), &unit), vec![*rolled.iter().max().unwrap()]); } }
+}
@@ -0,0 +1,259 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    rc::Rc };
+use crate::{
+    Name, Unit, Value, Values,
+    dice::Face,
+    random::{Rng, default_rng},
+    rollers::{DieRoll, Roll, RollWalker, Roller, SubRoll, SubRoller, finish::FinishedRoll},
+    units::BasicUnit };
+
+
+/// What makes two rolled faces a match
+#[derive(Clone, Debug)]
+pub enum GroupBy {
+    /// Faces match if they have the same label, no matter what they're worth
+    Label,
+    /// Faces match if they're worth the same for the `Unit`. Faces without a `Value` for it can't
+    /// match anything, so they're always waste.
+    Value(Rc<dyn Unit>),
+}
+
+
+/// A group of matching faces, such as three 7s, which One Roll Engine games write as "3x7"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchingSet {
+    /// The label of the faces, or their value if they were grouped by `Value`
+    pub label: String,
+    /// How much each of the faces is worth, if they were grouped by `Value`
+    pub height: Option<i32>,
+    /// How many faces are in the set
+    pub width: u32,
+}
+impl Display for MatchingSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}x{}", self.width, self.label)) }
+}
+
+
+/// Compares two sets for `SetPreference::Custom`
+pub type SetComparator = Rc<dyn Fn(&MatchingSet, &MatchingSet) -> Ordering>;
+
+
+/// Which `MatchingSet` is the best one when a roll has more than one
+#[derive(Clone)]
+pub enum SetPreference {
+    /// The widest set wins, with ties going to the highest
+    Widest,
+    /// The highest set wins, with ties going to the widest
+    Highest,
+    /// Compares two sets, where `Greater` means the first is better
+    Custom(SetComparator),
+}
+impl SetPreference {
+    pub fn custom(compare: impl Fn(&MatchingSet, &MatchingSet) -> Ordering + 'static) -> Self {
+        SetPreference::Custom(Rc::new(compare)) }
+
+    /// Compares two sets, where `Greater` means the first is better
+    pub fn compare(&self, set1: &MatchingSet, set2: &MatchingSet) -> Ordering {
+        match self {
+            SetPreference::Widest => set1.width.cmp(&set2.width).then(set1.height.cmp(&set2.height)),
+            SetPreference::Highest => set1.height.cmp(&set2.height).then(set1.width.cmp(&set2.width)),
+            SetPreference::Custom(compare) => compare(set1, set2) } }
+}
+
+
+/// The matching sets in a roll's faces, along with the waste: the faces that didn't match anything
+#[derive(Clone, Debug)]
+pub struct SetAnalysis {
+    sets: Vec<MatchingSet>,
+    waste: Vec<String>,
+}
+impl SetAnalysis {
+    /// Groups the rolled faces of the roll (such as a `PoolRoller`'s) into sets
+    pub fn of(roll: &dyn SubRoll, group_by: &GroupBy, preference: &SetPreference) -> Self {
+        Self::from_faces(
+            roll.rolled_faces().into_iter().map(|die_roll| die_roll.face().as_ref()),
+            group_by,
+            preference) }
+
+    /// Groups the faces into sets
+    pub fn from_faces<'a>(faces: impl IntoIterator<Item=&'a Face>, group_by: &GroupBy, preference: &SetPreference) -> Self {
+        let mut groups: Vec<MatchingSet> = Vec::new();
+        let mut waste = Vec::new();
+        for face in faces {
+            let (label, height) = match group_by {
+                GroupBy::Label => (face.label.to_string(), None),
+                GroupBy::Value(unit) => match face.value_for(unit) {
+                    Some(value) => (value.to_string(), Some(value)),
+                    None => {
+                        waste.push(face.label.to_string());
+                        continue } } };
+            match groups.iter_mut().find(|set| set.label == label) {
+                Some(set) => set.width += 1,
+                None => groups.push(MatchingSet { label, height, width: 1 }) } }
+        let (mut sets, singles): (Vec<MatchingSet>, Vec<MatchingSet>) = groups.into_iter()
+            .partition(|set| set.width > 1);
+        waste.extend(singles.into_iter().map(|set| set.label));
+        sets.sort_by(|set1, set2| preference.compare(set2, set1));
+        Self { sets, waste } }
+
+    /// The sets, from best to worst
+    pub fn sets(&self) -> &[MatchingSet] { &self.sets }
+
+    /// The best set, if there are any
+    pub fn best(&self) -> Option<&MatchingSet> { self.sets.first() }
+
+    /// The labels (or values) of the faces that didn't match any others
+    pub fn waste(&self) -> &[String] { &self.waste }
+}
+impl Display for SetAnalysis {
+    /// Lists the sets from best to worst, then the waste, such as "3x7, 2x4; waste: 1, 9"
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.sets.is_empty() {
+            f.write_str("no sets")?; }
+        else {
+            let sets = self.sets.iter()
+                .map(MatchingSet::to_string)
+                .collect::<Vec<String>>()
+                .join(", ");
+            f.write_str(&sets)?; }
+        if !self.waste.is_empty() {
+            f.write_fmt(format_args!("; waste: {}", self.waste.join(", ")))?; }
+        Ok(()) }
+}
+
+
+/// `MatchingSetsRoller` reads a pool (or any other `SubRoller`) for matching faces instead of
+/// adding them up, the way the One Roll Engine does. Its totals are the width and height of the
+/// best set, using its own `width_unit()` and `height_unit()`, so it can be used in stats and
+/// conditions like any other roller. The height is only there when grouping by `Value`. For
+/// everything about the sets, use `sets_roll()` and look at its `analysis()`.
+#[derive(Clone)]
+pub struct MatchingSetsRoller {
+    pool: Rc<dyn SubRoller>,
+    group_by: GroupBy,
+    preference: SetPreference,
+    width_unit: Rc<dyn Unit>,
+    height_unit: Rc<dyn Unit>,
+}
+impl MatchingSetsRoller {
+    /// Creates a `MatchingSetsRoller` that prefers the widest set
+    pub fn new(pool: Rc<dyn SubRoller>, group_by: GroupBy) -> Rc<Self> {
+        Rc::new(Self {
+            pool,
+            group_by,
+            preference: SetPreference::Widest,
+            width_unit: BasicUnit::new(Name::new("Width").unwrap(), "{} wide", true),
+            height_unit: BasicUnit::new(Name::new("Height").unwrap(), "{} high", false) }) }
+
+    /// Changes which set is picked as the best one
+    pub fn preferring(self: Rc<Self>, preference: SetPreference) -> Rc<Self> {
+        Rc::new(Self { preference, ..Rc::unwrap_or_clone(self) }) }
+
+    /// The `Unit` that the best set's width is given in
+    pub fn width_unit(&self) -> &Rc<dyn Unit> { &self.width_unit }
+
+    /// The `Unit` that the best set's height is given in
+    pub fn height_unit(&self) -> &Rc<dyn Unit> { &self.height_unit }
+
+    /// Does the same thing as `roll()`, except it returns the roll as a statically-typed `SetsRoll`
+    /// instead of a `dyn Roll`, giving access to its extra methods
+    pub fn sets_roll(self: Rc<Self>) -> Box<SetsRoll> {
+        self.sets_roll_with(default_rng()) }
+
+    /// Does the same thing as `roll_with()`, except it returns the roll as a statically-typed
+    /// `SetsRoll` instead of a `dyn Roll`, giving access to its extra methods
+    pub fn sets_roll_with(self: Rc<Self>, rng: Rng) -> Box<SetsRoll> {
+        let inner = self.pool.clone().inner_roll_with(rng);
+        let analysis = SetAnalysis::of(inner.as_ref(), &self.group_by, &self.preference);
+        Box::new(SetsRoll {
+            inner,
+            analysis,
+            width_unit: self.width_unit.clone(),
+            height_unit: self.height_unit.clone() }) }
+}
+impl Roller for MatchingSetsRoller {
+    fn description(&self) -> String {
+        format!("sets in {}", self.pool.inner_description()) }
+
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        FinishedRoll::new(self.inner_roll_with(rng)) }
+}
+impl SubRoller for MatchingSetsRoller {
+    fn is_simple(&self) -> bool { false }
+
+    fn inner_roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn SubRoll> {
+        self.sets_roll_with(rng) }
+}
+
+
+/// `SetsRoll` is the `Roll` created by `MatchingSetsRoller`
+pub struct SetsRoll {
+    inner: Box<dyn SubRoll>,
+    analysis: SetAnalysis,
+    width_unit: Rc<dyn Unit>,
+    height_unit: Rc<dyn Unit>,
+}
+impl SetsRoll {
+    /// All the sets and waste of the roll
+    pub fn analysis(&self) -> &SetAnalysis { &self.analysis }
+}
+impl Roll for SetsRoll {
+    /// Shows the rolled faces, then the sets, such as "d10:[7] + d10:[7] + d10:[1] => 2x7; waste: 1"
+    fn intermediate_results(&self) -> String {
+        format!("{} => {}", self.inner.inner_intermediate_results(), self.analysis) }
+
+    fn final_result(&self) -> String { self.totals().to_string() }
+
+    fn walk<'a>(&'a self, walker: &mut RollWalker<'a>) { self.inner.walk(walker) }
+}
+impl SubRoll for SetsRoll {
+    fn is_simple(&self) -> bool { false }
+
+    fn rolled_faces(&self) -> Vec<&DieRoll> { self.inner.rolled_faces() }
+
+    /// The width and height of the best set, or nothing if there aren't any sets
+    fn totals(&self) -> Values {
+        let mut out = Values::new();
+        if let Some(best) = self.analysis.best() {
+            out.add_value(Value::new(&self.width_unit, best.width as i32));
+            if let Some(height) = best.height {
+                out.add_value(Value::new(&self.height_unit, height)); } }
+        out }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        premade::number,
+        random::with_seed,
+        rollers::{GroupBy, MatchingSetsRoller, SetAnalysis, SetPreference, SubRoll, SubRoller} };
+
+    #[test]
+    fn finds_sets_and_waste() {
+        let (unit, dice) = number::build();
+        let d10 = &dice[5];
+        let faces = [4, 4, 4, 7, 7, 1, 9].map(|num| d10.faces[num - 1].as_ref());
+        let group_by = GroupBy::Value(unit);
+
+        let widest = SetAnalysis::from_faces(faces, &group_by, &SetPreference::Widest);
+        assert_eq!(widest.to_string(), "3x4, 2x7; waste: 1, 9");
+        let highest = SetAnalysis::from_faces(faces, &group_by, &SetPreference::Highest);
+        assert_eq!(highest.best().unwrap().height, Some(7));
+        assert_eq!(SetAnalysis::from_faces(faces, &GroupBy::Label, &SetPreference::Widest).best().unwrap().height, None); }
+
+    #[test]
+    fn totals_are_the_best_set() {
+        let (unit, dice) = number::build();
+        let sut = MatchingSetsRoller::new(dice[5].clone().n_times(6), GroupBy::Value(unit));
+        for seed in 0..10 {
+            let roll = sut.clone().sets_roll_with(with_seed(seed));
+            let analysis = roll.analysis();
+            let width = analysis.best().map_or(0, |best| best.width);
+            assert_eq!(roll.totals().value_for(sut.width_unit()).unwrap_or(0), width as i32);
+            let matched: u32 = analysis.sets().iter().map(|set| set.width).sum();
+            assert_eq!(matched as usize + analysis.waste().len(), 6); } }
+}
@@ -3,66 +3,105 @@ use std::{
     rc::Rc };
 use crate::{
     {Unit, Value, Values},
-    rollers::CollectedStats };
+    rollers::{CollectedStats, Condition, Histogram} };
 use crate::rollers::UnitStats;
 
+/// Gathers up the totals of each run, one at a time, without holding onto them. Each `Unit`'s
+/// totals are counted up in a `Histogram`, and the combinations of totals across `Unit`s are
+/// counted as outcomes (for probability queries), so the memory used depends on how many
+/// different totals come up rather than on the number of runs.
 pub(super) struct CollectedStatsBuilder {
-    stats: Vec<UnitStatsCalculator>
+    stats: Vec<UnitStatsCalculator>,
+    outcomes: HashMap<Vec<(u64, i32)>, (Values, u32)>,
+    num_runs: u32
 }
 impl CollectedStatsBuilder {
-    pub fn new(roll_vals: Vec<Values>) -> Self { 
-        let size = roll_vals.len();
-        let mut out = Self { stats: Vec::new() };
-        for values in roll_vals {
-            out.add_roll_values(values, size as u32);
-        }
-        out.finish();
-        out
-    }
+    pub fn new() -> Self {
+        Self { stats: Vec::new(), outcomes: HashMap::new(), num_runs: 0 } }
 
-    fn add_roll_values(&mut self, values: Values, size: u32) {
-        for value in values.into_iter() {
-            self.add_value(value.clone(), size) }
+    pub fn add_roll_values(&mut self, values: Values) {
+        self.num_runs += 1;
+        for value in &values {
+            self.add_value(value.clone()) }
+        self.outcomes.entry(outcome_key(&values))
+            .or_insert((values, 0))
+            .1 += 1;
     }
 
-    fn add_value(&mut self, value: Value, size: u32) {
+    pub fn num_runs(&self) -> u32 { self.num_runs }
+
+    /// The largest standard error of the average among the `Unit`s counted so far
+    pub fn largest_std_error(&self) -> f64 {
+        self.stats.iter()
+            .map(|rvalues| rvalues.std_error(self.num_runs))
+            .fold(0.0, f64::max) }
+
+    /// Fraction (0 to 1) of the runs so far that meet the condition
+    pub fn probability(&self, condition: &Condition) -> f64 {
+        if self.num_runs == 0 { return 0.0 }
+        let num_met: u64 = self.outcomes.values()
+            .filter(|(totals, _)| condition.is_met_by(totals))
+            .map(|(_, count)| *count as u64)
+            .sum();
+        num_met as f64 / self.num_runs as f64 }
+
+    fn add_value(&mut self, value: Value) {
         for rvalues in self.stats.iter_mut() {
             // attempts to add the Value and returns on success
             if rvalues.add_on_match(value.clone()) { 
                 return; } }
         // if we get here, there was no match, so we make a new one and add it
         self.stats.push(
-            UnitStatsCalculator::new(value, size));
+            UnitStatsCalculator::new(value));
     }
 
-    fn finish(&mut self) {
-        for rvalues in self.stats.iter_mut() {
-            rvalues.finish(); }
-    }
+    pub fn build(self) -> CollectedStats { self.build_keeping_values(true) }
 
-    pub fn build(self) -> CollectedStats {
+    /// Builds the stats without listing out every value in `UnitStats::values`, so memory use
+    /// stays the same no matter how many runs there were
+    pub fn build_streamed(self) -> CollectedStats { self.build_keeping_values(false) }
+
+    fn build_keeping_values(self, keep_values: bool) -> CollectedStats {
+        let num_runs = self.num_runs;
         CollectedStats { 
             stats: self.stats.into_iter()
-                .map(|rvalues| rvalues.calc_all())
-                .collect() } }
+                .map(|mut rvalues| {
+                    rvalues.finish(num_runs);
+                    rvalues.calc_all(keep_values) })
+                .collect(),
+            outcomes: self.outcomes.into_values().collect(),
+            num_runs } }
 }
 
 
+/// Totals that are the same except for the order of the `Unit`s or for having a `Unit`'s
+/// `default_value()` versus not having the `Unit` at all are the same outcome (see
+/// `Values::total_for()`), so they need the same key
+fn outcome_key(values: &Values) -> Vec<(u64, i32)> {
+    let mut key: Vec<(u64, i32)> = values.into_iter()
+        .filter(|value| value.value != value.unit.default_value())
+        .map(|value| (value.unit.id(), value.value))
+        .collect();
+    key.sort();
+    key }
+
+
+#[derive(Clone)]
 pub(super) struct UnitStatsCalculator {
     unit: Rc<dyn Unit>,
-    values: Vec<i32>
+    histogram: Histogram
 }
 
 impl UnitStatsCalculator {
-    fn new(value: Value, size: u32) -> Self {
-        let mut values = Vec::with_capacity(size as usize);
-        values.push(value.value);
-        Self { unit: value.unit, values }
+    fn new(value: Value) -> Self {
+        let mut histogram = Histogram::empty(value.unit.clone());
+        histogram.add(value.value, 1);
+        Self { unit: value.unit, histogram }
     }
 
     fn add_on_match(&mut self, value: Value) -> bool {
         if self.has_same_unit(value.unit) {
-            self.values.push(value.value);
+            self.histogram.add(value.value, 1);
             true }
         else {
             false } }
@@ -70,64 +109,204 @@ impl UnitStatsCalculator {
     pub fn has_same_unit(&self, relationship: Rc<dyn Unit>) -> bool {
         self.unit.id() == relationship.id() }
     
-    // Fill the rest of the space with 0s and sort the values    
-    fn finish(&mut self) {
-        let num_needed = self.values.capacity() - self.values.len();
-        for _ in 0..num_needed {
-            self.values.push(0); }
-        self.values.sort();
+    // Count the unit's default value for every run that didn't have a value for it
+    fn finish(&mut self, num_runs: u32) {
+        let num_needed = num_runs - self.histogram.num_runs();
+        if num_needed > 0 {
+            self.histogram.add(self.unit.default_value(), num_needed); }
     }
 
+    fn num_runs(&self) -> f64 { self.histogram.num_runs() as f64 }
+
+    /// The standard error of the average after `num_runs` runs, counting the runs it didn't show
+    /// up in as the `Unit`'s default value the same way `finish()` does, but without changing
+    /// the `Histogram`
+    fn std_error(&self, num_runs: u32) -> f64 {
+        let missing = (num_runs - self.histogram.num_runs()) as f64;
+        let default_value = self.unit.default_value() as f64;
+        let counts = || self.histogram.iter()
+            .map(|(total, count)| (*total as f64, *count as f64))
+            .chain(std::iter::once((default_value, missing)));
+        let average = counts().map(|(total, count)| total * count).sum::<f64>() / num_runs as f64;
+        let variance = counts()
+            .map(|(total, count)| (total - average).powi(2) * count)
+            .sum::<f64>() / num_runs as f64;
+        (variance / num_runs as f64).sqrt() }
+
+    /// Sums are done in f64, since adding up millions of f32s loses a lot of precision
     pub fn average(&self) -> f32 {
-        let sum = self.values.iter().sum::<i32>() as f32;
-        sum / (self.values.len() as f32) 
+        let sum = self.histogram.iter()
+            .map(|(total, count)| *total as f64 * *count as f64)
+            .sum::<f64>();
+        (sum / self.num_runs()) as f32
     }
 
+    /// Lists out every counted total, sorted from lowest to highest
+    fn values(&self) -> Vec<i32> {
+        self.histogram.iter()
+            .flat_map(|(total, count)| std::iter::repeat_n(*total, *count as usize))
+            .collect() }
+
     pub fn median(&self) -> f32 {
-        let mid = self.values.len() / 2;
-        if self.values.len().is_multiple_of(2) {
-            (self.values[mid - 1] + self.values[mid]) as f32 / 2.0 }
-        else {
-            self.values[mid] as f32 }
+        percentile(&self.histogram, 50.0)
     }
 
-    /// In the case that there are multiple mode values, an indeterminate "first"
-    /// value will be returned
+    /// In the case that there are multiple mode values, the lowest one is returned
     pub fn mode(&self) -> f32 {
-        let mut map = HashMap::new();
-        for value in self.values.iter() {
-            let count = map.entry(value).or_insert(0);
-            *count += 1;
-        }
-
-        let max_value = map.values().cloned().max().unwrap_or(0);
-
-        *map.into_iter()
-            .filter(|&(_, v)| v == max_value)
-            .map(|(k, _)| k)
-            .nth(0).unwrap() as f32
+        let mut mode = (0, 0);
+        for (total, count) in self.histogram.iter() {
+            if *count > mode.1 {
+                mode = (*total, *count); } }
+        mode.0 as f32
     }
 
     /// Asks for the average because it has most likely been calculated already,
     /// and it would be silly to recalculate it and questionable to cache the 
     /// calculation internally
     pub fn std_deviation(&self, avg: f32) -> f32 {
-        self.values.iter()
-            // collect distance from the average squared
-            .map(|val| (avg - (*val as f32)).powi(2))
-            // add them up and divide by the length - this gives the variance
-            .sum::<f32>() / (self.values.len() as f32)
-            // square root to finish with standard deviation
-            .sqrt() }
-
-    pub fn calc_all(self) -> UnitStats {
+        self.central_moment(avg, 2).sqrt() }
+
+    /// Like the standard deviation, asks for the average to avoid recalculating it. Gives the
+    /// population skewness, which is 0 for symmetrical results, positive when there's a long tail
+    /// of high results, and negative when the long tail is of low ones.
+    pub fn skewness(&self, avg: f32, std_deviation: f32) -> f32 {
+        if std_deviation == 0.0 { return 0.0 }
+        self.central_moment(avg, 3) / std_deviation.powi(3) }
+
+    /// The average of each total's distance from the average raised to the given power
+    fn central_moment(&self, avg: f32, power: i32) -> f32 {
+        let sum = self.histogram.iter()
+            .map(|(total, count)| (*total as f64 - avg as f64).powi(power) * *count as f64)
+            .sum::<f64>();
+        (sum / self.num_runs()) as f32 }
+
+    #[allow(deprecated)]
+    pub fn calc_all(self, keep_values: bool) -> UnitStats {
         let average = self.average();
+        let std_deviation = self.std_deviation(average);
         UnitStats {
+            values: if keep_values { self.values() } else { Vec::new() },
             average,
             median: self.median(),
             mode: self.mode(),
-            std_deviation: self.std_deviation(average),
+            std_deviation,
+            min: self.histogram.min().unwrap_or(0),
+            max: self.histogram.max().unwrap_or(0),
+            variance: std_deviation.powi(2),
+            skewness: self.skewness(average, std_deviation),
+            std_error: std_deviation / (self.num_runs() as f32).sqrt(),
             unit: self.unit.clone(),
-            values: self.values, }
+            histogram: self.histogram, }
     }
 }
+
+
+/// Finds the given percentile (0 to 100) of the counted totals, interpolating between the two
+/// closest totals when it falls between them, the same way the median does.
+pub(super) fn percentile(histogram: &Histogram, percent: f32) -> f32 {
+    let num_runs = histogram.num_runs();
+    if num_runs == 0 { return 0.0 }
+    let rank = (percent.clamp(0.0, 100.0) as f64 / 100.0) * (num_runs - 1) as f64;
+    let lower = nth_total(histogram, rank.floor() as u32);
+    let upper = nth_total(histogram, rank.ceil() as u32);
+    (lower as f64 + (upper as f64 - lower as f64) * rank.fract()) as f32 }
+
+
+/// The total that would be at the given index if every total were listed out in order
+fn nth_total(histogram: &Histogram, idx: u32) -> i32 {
+    let mut seen = 0;
+    for (total, count) in histogram.iter() {
+        seen += count;
+        if seen > idx { return *total } }
+    histogram.max().unwrap_or(0) }
+
+
+/// The number of standard errors on either side of the mean needed to cover the given confidence
+/// level (0 to 1), i.e. 1.96 for 0.95. Uses Acklam's approximation of the inverse of the normal
+/// distribution, which is far more accurate than the stats it's used on.
+pub(super) fn z_score(confidence: f32) -> f32 {
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+                         1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+                         6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+                         -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+                         3.754408661907416e+00];
+    // the upper tail's cutoff, so only one half of the approximation is needed
+    let p = (1.0 - (1.0 - confidence as f64).clamp(1e-12, 1.0) / 2.0).min(1.0 - 1e-12);
+    let z = if p <= 1.0 - 0.02425 {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+            (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0) }
+    else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0) };
+    z as f32
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZero, rc::Rc};
+    use crate::{
+        Name, Unit, Value,
+        dice::{Die, Face},
+        premade::number,
+        random::with_seed,
+        rollers::{Histogram, SubRoller, stats::calculate::{percentile, z_score}},
+        units::{BasicUnit, DNumUnit} };
+
+    #[test]
+    fn percentile_interpolates() {
+        let unit: Rc<dyn Unit> = DNumUnit::new();
+        let histogram = Histogram::from_values(unit.clone(), &[4, 1, 3, 2]);
+        assert_eq!(percentile(&histogram, 0.0), 1.0);
+        assert_eq!(percentile(&histogram, 50.0), 2.5);
+        assert_eq!(percentile(&histogram, 100.0), 4.0);
+
+        let extremes = Histogram::from_values(unit, &[i32::MIN, i32::MAX]);
+        assert_eq!(percentile(&extremes, 50.0), -0.5); }
+
+    #[test]
+    fn totals_far_apart() {
+        let unit: Rc<dyn Unit> = BasicUnit::new(Name::new("Far").unwrap(), "{}", false).defaulting_to(i32::MIN);
+        let faces = vec![
+            Face::new(Name::new("Miss").unwrap(), vec![]),
+            Face::with_one_val(Name::new("Max").unwrap(), Value::new(&unit, i32::MAX))];
+        let die = Die::new(Name::new("Coin").unwrap(), faces);
+        let stats = die.get_stats(NonZero::new(10).unwrap()).statistics_roll_with(with_seed(3));
+        let stats = stats.stats_for(unit).unwrap();
+        assert_eq!((stats.min, stats.max), (i32::MIN, i32::MAX));
+        assert_eq!(stats.histogram.iter().count(), 2); }
+
+    #[test]
+    fn streaming_matches_kept_rolls() {
+        let (unit, dice) = number::build();
+        let unit: Rc<dyn Unit> = unit;
+        let kept = dice[3].clone().get_stats(NonZero::new(1_000).unwrap())
+            .statistics_roll_with(with_seed(5));
+        let streamed = dice[3].clone().get_stats(NonZero::new(1_000).unwrap())
+            .streaming()
+            .statistics_roll_with(with_seed(5));
+
+        assert_eq!(kept.rolls().len(), 1_000);
+        assert!(streamed.rolls().is_empty());
+        let (kept, streamed) = (kept.stats_for(unit.clone()).unwrap(), streamed.stats_for(unit).unwrap());
+        assert_eq!(kept.values(), streamed.values());
+        #[allow(deprecated)]
+        let (kept_values, streamed_values) = (&kept.values, &streamed.values);
+        assert_eq!(kept_values, &kept.values());
+        assert!(streamed_values.is_empty());
+        assert_eq!(kept.average, streamed.average);
+        assert_eq!(kept.median, streamed.median);
+        assert_eq!(kept.std_deviation, streamed.std_deviation); }
+
+    #[test]
+    fn z_score_matches_tables() {
+        assert!((z_score(0.95) - 1.95996).abs() < 1e-4);
+        assert!((z_score(0.99) - 2.57583).abs() < 1e-4);
+        assert!((z_score(0.5) - 0.67449).abs() < 1e-4); }
+}
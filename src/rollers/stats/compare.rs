@@ -0,0 +1,185 @@
+use std::{
+    num::NonZero,
+    ops::Deref,
+    rc::Rc };
+use crate::{
+    Name, Unit,
+    random::{Rng, default_rng},
+    rollers::{Condition, Histogram, Roll, Roller, StatisticsRoll, StatsRoller, SubRoller} };
+
+
+/// `ComparisonRoller` runs two or more rollers through a `StatsRoller` side by side so that you
+/// can see what difference a change makes, such as adding a Proficiency die versus removing a
+/// Setback die. Each roller gets the same seed, so they're all rolled with the same random
+/// numbers, which keeps luck from making one look better than it is.
+///
+/// The first roller is the baseline. The report in `final_result()` shows, for each `Unit`, how
+/// the others differ from it in average and in the chance of getting at least 1, along with how
+/// often each of them beats the baseline outright.
+pub struct ComparisonRoller {
+    runs: NonZero<u32>,
+    rollers: Vec<(Name, Rc<dyn SubRoller>)>,
+}
+impl ComparisonRoller {
+    /// Creates a `ComparisonRoller` that runs each of the named rollers `num_runs` times. The first
+    /// one is used as the baseline, so if there aren't any rollers, this returns None.
+    pub fn new(rollers: Vec<(Name, Rc<dyn SubRoller>)>, num_runs: NonZero<u32>) -> Option<Rc<Self>> {
+        if rollers.is_empty() { None }
+        else { Some(Rc::new(Self { runs: num_runs, rollers })) } }
+
+    /// Creates a `ComparisonRoller` for just two rollers, with the first one as the baseline
+    pub fn pair(
+        baseline_name: Name, baseline: Rc<dyn SubRoller>,
+        other_name: Name, other: Rc<dyn SubRoller>,
+        num_runs: NonZero<u32>
+    ) -> Rc<Self> {
+        Rc::new(Self { runs: num_runs, rollers: vec![(baseline_name, baseline), (other_name, other)] }) }
+
+    /// Does the same thing as `roll()`, except it returns the roll as a statically-typed
+    /// `ComparisonRoll` instead of a `dyn Roll`, giving access to its extra methods
+    pub fn comparison_roll(self: Rc<Self>) -> Box<ComparisonRoll> {
+        self.comparison_roll_with(default_rng()) }
+
+    /// Does the same thing as `roll_with()`, except it returns the roll as a statically-typed
+    /// `ComparisonRoll` instead of a `dyn Roll`, giving access to its extra methods
+    pub fn comparison_roll_with(self: Rc<Self>, mut rng: Rng) -> Box<ComparisonRoll> {
+        let seed = rng.next();
+        Box::new(ComparisonRoll {
+            runs: self.runs.get(),
+            entries: self.rollers.iter()
+                .map(|(name, roller)| (
+                    name.clone(),
+                    StatsRoller::new(roller.clone(), self.runs)
+                        .statistics_roll_with(Rng::from_seed(seed))))
+                .collect() }) }
+}
+impl Roller for ComparisonRoller {
+    fn description(&self) -> String {
+        let rollers = self.rollers.iter()
+            .map(|(name, roller)| format!("{}: {}", name, roller.description()))
+            .collect::<Vec<String>>()
+            .join(" vs ");
+        format!("Compares {} over {} runs", rollers, self.runs) }
+
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        self.comparison_roll_with(rng) }
+}
+
+
+/// `ComparisonRoll` is the `Roll` created by `ComparisonRoller`, holding the `StatisticsRoll` of
+/// each of the compared rollers, in the same order they were given.
+pub struct ComparisonRoll {
+    runs: u32,
+    entries: Vec<(Name, Box<StatisticsRoll>)>,
+}
+impl ComparisonRoll {
+    /// Iterates through the name and `StatisticsRoll` of each compared roller
+    pub fn entries(&self) -> impl Iterator<Item=(&Name, &StatisticsRoll)> {
+        self.entries.iter().map(|(name, stats)| (name, stats.deref())) }
+
+    /// Every `Unit` that showed up in any of the rolls, in the order they were first seen
+    pub fn units(&self) -> Vec<Rc<dyn Unit>> {
+        let mut units: Vec<Rc<dyn Unit>> = Vec::new();
+        for (_, stats) in self.entries.iter() {
+            for rstats in stats.collected_stats().stats.iter() {
+                if !units.iter().any(|unit| rstats.has_same_unit(unit.clone())) {
+                    units.push(rstats.unit.clone()); } } }
+        units }
+
+    /// The average of the `Unit` for each roller minus the baseline's average
+    pub fn average_differences(&self, unit: &Rc<dyn Unit>) -> Vec<f32> {
+        let baseline = self.average(0, unit);
+        (0..self.entries.len())
+            .map(|idx| self.average(idx, unit) - baseline)
+            .collect() }
+
+    /// The chance of the `Condition` being met for each roller
+    pub fn probabilities(&self, condition: &Condition) -> Vec<f64> {
+        self.entries.iter()
+            .map(|(_, stats)| stats.probability(condition))
+            .collect() }
+
+    /// The chance that a roll from the roller at the first index would come up with a higher total
+    /// for the `Unit` than a separate roll from the roller at the second index, followed by the
+    /// chance that they'd tie. Since the compared rollers share random numbers, their runs are
+    /// matched up as if they were independent, using the distribution of each.
+    pub fn probability_beats(&self, idx: usize, other_idx: usize, unit: &Rc<dyn Unit>) -> (f64, f64) {
+        let mine = self.histogram(idx, unit);
+        let theirs = self.histogram(other_idx, unit);
+        let mut beats = 0.0;
+        let mut ties = 0.0;
+        for (total, _) in mine.iter() {
+            let prob = mine.proportion_of(*total);
+            beats += prob * (1.0 - theirs.proportion_at_least(*total));
+            ties += prob * theirs.proportion_of(*total); }
+        (beats, ties) }
+
+    /// Rollers that never rolled the `Unit` count as having rolled its `default_value()` every time
+    fn average(&self, idx: usize, unit: &Rc<dyn Unit>) -> f32 {
+        self.entries[idx].1.stats_for(unit.clone()).map_or(unit.default_value() as f32, |rstats| rstats.average) }
+
+    /// Rollers that never rolled the `Unit` count as having rolled its `default_value()` every time
+    fn histogram(&self, idx: usize, unit: &Rc<dyn Unit>) -> Histogram {
+        match self.entries[idx].1.stats_for(unit.clone()) {
+            Some(rstats) => rstats.histogram.clone(),
+            None => Histogram::from_values(unit.clone(), &vec![unit.default_value(); self.runs as usize]) } }
+}
+impl Roll for ComparisonRoll {
+    /// Simply returns "Compared # rollers over # runs each"
+    fn intermediate_results(&self) -> String {
+        format!("Compared {} rollers over {} runs each", self.entries.len(), self.runs) }
+
+    fn final_result(&self) -> String {
+        let baseline_name = &self.entries[0].0;
+        let mut out = format!("{}:\n", self.intermediate_results());
+        for unit in self.units() {
+            out += &format!("{}:\n", unit);
+            let at_least_one = self.probabilities(&Condition::at_least(&unit, 1));
+            let differences = self.average_differences(&unit);
+            for (idx, (name, _)) in self.entries.iter().enumerate() {
+                out += &format!(
+                    "  {}: average {:.2}, at least 1: {:.1}%",
+                    name, self.average(idx, &unit), at_least_one[idx] * 100.0);
+                if idx > 0 {
+                    let (beats, ties) = self.probability_beats(idx, 0, &unit);
+                    out += &format!(
+                        " ({:+.2}, {:+.1}%), beats {}: {:.1}%, ties: {:.1}%",
+                        differences[idx],
+                        (at_least_one[idx] - at_least_one[0]) * 100.0,
+                        baseline_name,
+                        beats * 100.0,
+                        ties * 100.0); }
+                out += "\n"; } }
+        out }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZero, rc::Rc};
+    use crate::{
+        Name, Unit,
+        premade::number,
+        random::with_seed,
+        rollers::{ComparisonRoller, SubRoller} };
+
+    #[test]
+    fn d6_beats_d4() {
+        let (unit, dice) = number::build();
+        let unit: Rc<dyn Unit> = unit;
+        let d4: Rc<dyn SubRoller> = dice[2].clone();
+        let d6: Rc<dyn SubRoller> = dice[3].clone();
+        let sut = ComparisonRoller::pair(
+                Name::new("d4").unwrap(), d4,
+                Name::new("d6").unwrap(), d6,
+                NonZero::new(20_000).unwrap())
+            .comparison_roll_with(with_seed(3));
+
+        let differences = sut.average_differences(&unit);
+        assert_eq!(differences[0], 0.0);
+        assert!((differences[1] - 1.0).abs() < 0.1);
+        // d6 > d4 happens 14 times out of 24, and they tie 4 times out of 24
+        let (beats, ties) = sut.probability_beats(1, 0, &unit);
+        assert!((beats - 14.0 / 24.0).abs() < 0.02);
+        assert!((ties - 4.0 / 24.0).abs() < 0.02); }
+}
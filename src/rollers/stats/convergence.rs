@@ -0,0 +1,167 @@
+use std::{
+    fmt::{Display, Formatter},
+    num::NonZero };
+use crate::rollers::{Condition, stats::calculate::CollectedStatsBuilder};
+
+
+/// What a `StatsRoller` keeps an eye on to decide whether it has rolled enough
+#[derive(Clone, Debug)]
+pub enum ConvergenceTarget {
+    /// The standard error of every `Unit`'s average
+    Averages,
+    /// The standard error of the chance of the `Condition` being met. It's estimated as if 2 more
+    /// runs had met it and 2 more hadn't (the Agresti-Coull "plus four" estimate), so a rare event
+    /// that hasn't come up yet doesn't look perfectly precise after the first batch.
+    Probability(Condition)
+}
+impl Display for ConvergenceTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvergenceTarget::Averages => f.write_str("the averages"),
+            ConvergenceTarget::Probability(_) => f.write_str("the probability") } }
+}
+
+
+/// Settings for a `StatsRoller` that keeps rolling until its stats are precise enough instead of
+/// rolling a set number of times. After each batch of runs, the standard error of the target is
+/// checked against the tolerance, and rolling stops once it's at or below it or once `max_runs`
+/// is reached, whichever comes first. It never counts as converged before `MIN_RUNS` runs, though.
+///
+/// For example, a tolerance of 0.01 on the averages means each average is very likely (about 95%)
+/// to be within 0.02 of the true average.
+#[derive(Clone, Debug)]
+pub struct Convergence {
+    pub target: ConvergenceTarget,
+    pub tolerance: f64,
+    pub max_runs: NonZero<u32>,
+}
+impl Convergence {
+    /// How many runs it takes before either target can count as converged. A small sample can look
+    /// far more precise than it is, such as when a `Unit` hasn't come up yet (its average can't be
+    /// checked at all) or hasn't varied yet (its standard error looks like 0).
+    pub const MIN_RUNS: u32 = 100;
+
+    /// Stops once the standard error of every `Unit`'s average is at most `tolerance`
+    pub fn of_averages(tolerance: f64, max_runs: NonZero<u32>) -> Self {
+        Self { target: ConvergenceTarget::Averages, tolerance, max_runs } }
+
+    /// Stops once the standard error of the chance of the `Condition` being met is at most
+    /// `tolerance`, which is a fraction (0 to 1) like the chance itself
+    pub fn of_probability(condition: Condition, tolerance: f64, max_runs: NonZero<u32>) -> Self {
+        Self { target: ConvergenceTarget::Probability(condition), tolerance, max_runs } }
+
+    pub(super) fn std_error(&self, builder: &CollectedStatsBuilder) -> f64 {
+        match &self.target {
+            ConvergenceTarget::Averages => builder.largest_std_error(),
+            ConvergenceTarget::Probability(condition) => {
+                let num_runs = builder.num_runs() as f64 + 4.0;
+                let prob = (builder.probability(condition) * builder.num_runs() as f64 + 2.0) / num_runs;
+                (prob * (1.0 - prob) / num_runs).sqrt() } } }
+
+    pub(super) fn precision(&self, builder: &CollectedStatsBuilder) -> Precision {
+        let std_error = self.std_error(builder);
+        Precision {
+            target: self.target.clone(),
+            std_error,
+            tolerance: self.tolerance,
+            num_runs: builder.num_runs(),
+            converged: builder.num_runs() >= Self::MIN_RUNS && std_error <= self.tolerance } }
+}
+
+
+/// How precise the stats of a `StatsRoller` using `Convergence` ended up being
+#[derive(Clone, Debug)]
+pub struct Precision {
+    pub target: ConvergenceTarget,
+    /// The standard error that was reached (the largest one, when checking the averages)
+    pub std_error: f64,
+    pub tolerance: f64,
+    pub num_runs: u32,
+    /// Whether the tolerance was reached before running out of runs
+    pub converged: bool,
+}
+impl Display for Precision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.converged {
+            f.write_fmt(format_args!(
+                "Precision: standard error of {} reached {:.4} (tolerance {}) after {} runs",
+                self.target, self.std_error, self.tolerance, self.num_runs)) }
+        else {
+            f.write_fmt(format_args!(
+                "Precision: standard error of {} only reached {:.4} (tolerance {}) by the limit of {} runs",
+                self.target, self.std_error, self.tolerance, self.num_runs)) } }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZero, rc::Rc};
+    use crate::{
+        Unit, Value, Values,
+        premade::number,
+        random::with_seed,
+        rollers::{Condition, Convergence, StatsRoller, SubRoller} };
+
+    #[test]
+    fn stops_once_precise_enough() {
+        let (unit, dice) = number::build();
+        let unit: Rc<dyn Unit> = unit;
+        let convergence = Convergence::of_averages(0.05, NonZero::new(1_000_000).unwrap());
+        let sut = dice[3].clone().get_stats(NonZero::new(100).unwrap())
+            .until_converged(convergence)
+            .statistics_roll_with(with_seed(8));
+
+        // a d6 has a standard deviation of about 1.71, so it takes about 1,170 runs
+        let precision = sut.precision().unwrap();
+        assert!(precision.converged);
+        assert!(precision.std_error <= 0.05);
+        assert!((1_100..=1_300).contains(&precision.num_runs));
+        assert!((sut.stats_for(unit).unwrap().average - 3.5).abs() < 0.15); }
+
+    #[test]
+    fn stops_at_max_runs() {
+        let (unit, dice) = number::build();
+        let unit: Rc<dyn Unit> = unit;
+        let convergence = Convergence::of_probability(
+            Condition::at_least(&unit, 5), 0.0001, NonZero::new(250).unwrap());
+        let sut = dice[3].clone().get_stats(NonZero::new(100).unwrap())
+            .until_converged(convergence)
+            .statistics_roll_with(with_seed(8));
+
+        let precision = sut.precision().unwrap();
+        assert!(!precision.converged);
+        assert_eq!(precision.num_runs, 250);
+        assert_eq!(sut.collected_stats().num_runs(), 250); }
+
+    #[test]
+    fn small_samples_never_converge() {
+        let (unit, _) = number::build();
+        let unit: Rc<dyn Unit> = unit;
+        // never varies, so its standard error is 0 from the very first run
+        let constant = Values::from(Value::new(&unit, 3)).to_roller();
+        let convergence = Convergence::of_averages(0.05, NonZero::new(1_000).unwrap());
+        let sut = StatsRoller::new(constant, NonZero::new(10).unwrap())
+            .until_converged(convergence)
+            .statistics_roll_with(with_seed(8));
+
+        let precision = sut.precision().unwrap();
+        assert!(precision.converged);
+        assert_eq!(precision.std_error, 0.0);
+        assert_eq!(precision.num_runs, Convergence::MIN_RUNS); }
+
+    #[test]
+    fn rare_events_need_more_than_one_batch() {
+        let (unit, dice) = number::build();
+        let unit: Rc<dyn Unit> = unit;
+        let natural_20 = Condition::at_least(&unit, 20);
+        let convergence = Convergence::of_probability(natural_20.clone(), 0.01, NonZero::new(100_000).unwrap());
+        let sut = dice[7].clone().get_stats(NonZero::new(10).unwrap())
+            .until_converged(convergence)
+            .statistics_roll_with(with_seed(8));
+
+        // a 5% chance needs about 475 runs to get a standard error of 0.01
+        let precision = sut.precision().unwrap();
+        assert!(precision.converged);
+        assert!((300..=800).contains(&precision.num_runs), "took {} runs", precision.num_runs);
+        assert!((sut.probability(&natural_20) - 0.05).abs() < 0.03); }
+}
@@ -0,0 +1,154 @@
+use std::rc::Rc;
+use crate::{
+    {Unit, Values},
+    rollers::{CollectedStats, UnitStats},
+    serialize::{csv_field, json_string} };
+
+
+/// A column of the summary table, as its CSV header and JSON key, along with how to get it
+type SummaryColumn = (&'static str, &'static str, fn(&UnitStats) -> f32);
+
+const SUMMARY_COLUMNS: [SummaryColumn; 10] = [
+    ("Runs", "runs", |rstats| rstats.histogram.num_runs() as f32),
+    ("Average", "average", |rstats| rstats.average),
+    ("Median", "median", |rstats| rstats.median),
+    ("Mode", "mode", |rstats| rstats.mode),
+    ("Standard Deviation", "std_deviation", |rstats| rstats.std_deviation),
+    ("Minimum", "min", |rstats| rstats.min as f32),
+    ("Maximum", "max", |rstats| rstats.max as f32),
+    ("Variance", "variance", |rstats| rstats.variance),
+    ("Skewness", "skewness", |rstats| rstats.skewness),
+    ("Standard Error", "std_error", |rstats| rstats.std_error) ];
+
+
+impl CollectedStats {
+    /// Writes the stats out as CSV, with a header row followed by one row per `Unit`. The first
+    /// column is the `Unit`'s name, as it's displayed.
+    pub fn to_csv(&self) -> String {
+        let header = std::iter::once("Unit")
+            .chain(SUMMARY_COLUMNS.iter().map(|(header, _, _)| *header))
+            .map(csv_field)
+            .collect::<Vec<String>>()
+            .join(",");
+        let rows = self.stats.iter()
+            .map(|rstats| std::iter::once(csv_field(&rstats.unit.to_string()))
+                .chain(SUMMARY_COLUMNS.iter().map(|(_, _, stat)| stat(rstats).to_string()))
+                .collect::<Vec<String>>()
+                .join(","));
+        std::iter::once(header).chain(rows)
+            .map(|line| line + "\n")
+            .collect() }
+
+    /// Writes the stats out as a JSON array with an object for each `Unit`, holding the `Unit`'s
+    /// displayed name under "unit" and each stat under its snake_case name, such as
+    /// "std_deviation".
+    pub fn to_json(&self) -> String {
+        let objects = self.stats.iter()
+            .map(|rstats| {
+                let fields = std::iter::once(format!("\"unit\":{}", json_string(&rstats.unit.to_string())))
+                    .chain(SUMMARY_COLUMNS.iter()
+                        .map(|(_, key, stat)| format!("\"{}\":{}", key, json_number(stat(rstats)))))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                format!("{{{}}}", fields) })
+            .collect::<Vec<String>>();
+        format!("[{}]", objects.join(",")) }
+}
+
+
+/// Writes the totals of each run as CSV, with a column for each `Unit` (headed by its displayed
+/// name) and a row for each run. Runs without a `Value` for a `Unit` get its
+/// `default_value()`.
+pub(super) fn samples_to_csv(runs: &[Values]) -> String {
+    let units = units_of(runs);
+    let header = units.iter()
+        .map(|unit| csv_field(&unit.to_string()))
+        .collect::<Vec<String>>()
+        .join(",");
+    let rows = runs.iter()
+        .map(|totals| units.iter()
+            .map(|unit| totals.total_for(unit).to_string())
+            .collect::<Vec<String>>()
+            .join(","));
+    std::iter::once(header).chain(rows)
+        .map(|line| line + "\n")
+        .collect() }
+
+
+/// Writes the totals of each run as a JSON array with an object for each run, keyed by the
+/// displayed names of the `Unit`s. Runs without a `Value` for a `Unit` get its
+/// `default_value()`.
+pub(super) fn samples_to_json(runs: &[Values]) -> String {
+    let units = units_of(runs);
+    let objects = runs.iter()
+        .map(|totals| {
+            let fields = units.iter()
+                .map(|unit| format!(
+                    "{}:{}",
+                    json_string(&unit.to_string()),
+                    totals.total_for(unit)))
+                .collect::<Vec<String>>()
+                .join(",");
+            format!("{{{}}}", fields) })
+        .collect::<Vec<String>>();
+    format!("[{}]", objects.join(",")) }
+
+
+/// Every `Unit` in the runs, in the order they were first seen
+fn units_of(runs: &[Values]) -> Vec<Rc<dyn Unit>> {
+    let mut units: Vec<Rc<dyn Unit>> = Vec::new();
+    for value in runs.iter().flatten() {
+        if !units.iter().any(|unit| unit.id() == value.unit.id()) {
+            units.push(value.unit.clone()); } }
+    units }
+
+
+/// JSON has no way to write NaN or infinity, so those become null
+fn json_number(num: f32) -> String {
+    if num.is_finite() { num.to_string() }
+    else { "null".to_owned() } }
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit, Value, Values,
+        rollers::stats::{calculate::CollectedStatsBuilder, export::{samples_to_csv, samples_to_json}},
+        units::BasicUnit };
+
+    fn unit(name: &str) -> Rc<dyn Unit> {
+        BasicUnit::new(Name::new(name).unwrap(), "{}", false) }
+
+    fn runs() -> Vec<Values> {
+        let success = unit("Success");
+        let threat = unit("Threat, Minor");
+        vec![
+            Values::from(vec![
+                Value { unit: success.clone(), value: 2 },
+                Value { unit: threat.clone(), value: 1 }]),
+            Values::from(Value { unit: success.clone(), value: 1 }),
+            Values::from(Value { unit: threat.clone(), value: 3 })] }
+
+    #[test]
+    fn samples_have_a_column_per_unit() {
+        assert_eq!(samples_to_csv(&runs()), "Success,\"Threat, Minor\"\n2,1\n1,0\n0,3\n");
+        assert_eq!(
+            samples_to_json(&runs()),
+            "[{\"Success\":2,\"Threat, Minor\":1},{\"Success\":1,\"Threat, Minor\":0},\
+             {\"Success\":0,\"Threat, Minor\":3}]"); }
+
+    #[test]
+    fn summary_has_a_row_per_unit() {
+        let mut builder = CollectedStatsBuilder::new();
+        for totals in runs() {
+            builder.add_roll_values(totals); }
+        let sut = builder.build();
+
+        assert_eq!(
+            sut.to_csv(),
+            "Unit,Runs,Average,Median,Mode,Standard Deviation,Minimum,Maximum,Variance,Skewness,Standard Error\n\
+             Success,3,1,1,0,0.8164966,0,2,0.6666667,0,0.47140455\n\
+             \"Threat, Minor\",3,1.3333334,1,0,1.2472191,0,3,1.5555555,0.38180172,0.7200823\n");
+        assert!(sut.to_json().starts_with("[{\"unit\":\"Success\",\"runs\":3,\"average\":1,\"median\":1,")); }
+}
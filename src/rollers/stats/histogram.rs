@@ -0,0 +1,208 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    rc::Rc };
+use crate::Unit;
+
+
+/// How many times each total came up for a single `Unit` over all the runs of a `StatsRoller`.
+/// Only the totals that actually came up are kept, so it takes the same memory however far apart
+/// they are.
+#[derive(Clone)]
+pub struct Histogram {
+    pub unit: Rc<dyn Unit>,
+    counts: BTreeMap<i32, u32>,
+    num_runs: u32,
+}
+impl Histogram {
+    /// Counts up the given totals for the `Unit`. They don't need to be in any particular order.
+    pub fn from_values(unit: Rc<dyn Unit>, values: &[i32]) -> Self {
+        let mut histogram = Self::empty(unit);
+        for value in values {
+            histogram.add(*value, 1); }
+        histogram }
+
+    pub(super) fn empty(unit: Rc<dyn Unit>) -> Self {
+        Self { unit, counts: BTreeMap::new(), num_runs: 0 } }
+
+    /// Counts the total `count` more times
+    pub(super) fn add(&mut self, total: i32, count: u32) {
+        *self.counts.entry(total).or_insert(0) += count;
+        self.num_runs += count; }
+
+    /// Number of runs that were counted
+    pub fn num_runs(&self) -> u32 { self.num_runs }
+
+    /// The lowest total that came up, or None if nothing was counted
+    pub fn min(&self) -> Option<i32> { self.counts.keys().next().copied() }
+
+    /// The highest total that came up, or None if nothing was counted
+    pub fn max(&self) -> Option<i32> { self.counts.keys().next_back().copied() }
+
+    /// Number of times the given total came up
+    pub fn count_of(&self, total: i32) -> u32 {
+        self.counts.get(&total).copied().unwrap_or(0) }
+
+    /// Fraction (0 to 1) of the runs that came up with the given total
+    pub fn proportion_of(&self, total: i32) -> f64 {
+        self.fraction(self.count_of(total)) }
+
+    /// Fraction (0 to 1) of the runs that came up with the given total or higher
+    pub fn proportion_at_least(&self, total: i32) -> f64 {
+        self.fraction(self.counts.range(total..).map(|(_, count)| count).sum()) }
+
+    /// Iterates through each total that came up and the number of times it did, from lowest to
+    /// highest
+    pub fn iter(&self) -> impl Iterator<Item=(&i32, &u32)> { self.counts.iter() }
+
+    /// Draws the histogram as a horizontal bar chart, one row per total that came up, with bars up
+    /// to `width` characters long for the largest proportion.
+    pub fn render(&self, chart: &Chart) -> String {
+        let proportions: Vec<(i32, f64)> = match chart.kind {
+            ChartKind::Frequency => self.counts.iter()
+                .map(|(total, count)| (*total, self.fraction(*count)))
+                .collect(),
+            ChartKind::AtLeast => self.counts.keys()
+                .map(|total| (*total, self.proportion_at_least(*total)))
+                .collect() };
+        let largest = proportions.iter().map(|(_, prop)| *prop).fold(0.0, f64::max);
+        let label_width = proportions.iter()
+            .map(|(total, _)| chart.kind.label(*total).len())
+            .max()
+            .unwrap_or(0);
+
+        proportions.iter()
+            .map(|(total, prop)| {
+                let length = if largest > 0.0 { prop / largest * chart.width as f64 } else { 0.0 };
+                format!(
+                    "{:>label_width$} | {:<bar_width$} {:5.1}%",
+                    chart.kind.label(*total),
+                    chart.style.bar(length),
+                    prop * 100.0,
+                    bar_width = chart.width) })
+            .collect::<Vec<String>>()
+            .join("\n") }
+
+    fn fraction(&self, count: u32) -> f64 {
+        if self.num_runs == 0 { 0.0 }
+        else { count as f64 / self.num_runs as f64 } }
+}
+impl Display for Histogram {
+    /// Draws the default `Chart` with the `Unit`'s name above it
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}:\n{}", self.unit, self.render(&Chart::default()))) }
+}
+
+
+/// What each bar of a `Chart` shows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartKind {
+    /// How often each total came up
+    Frequency,
+    /// How often each total or higher came up
+    AtLeast
+}
+impl ChartKind {
+    fn label(&self, total: i32) -> String {
+        match self {
+            ChartKind::Frequency => total.to_string(),
+            ChartKind::AtLeast => format!("{}+", total) } }
+}
+
+
+/// What characters the bars of a `Chart` are drawn with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartStyle {
+    /// Bars made of '#', rounded to the nearest whole character
+    Ascii,
+    /// Bars made of Unicode block elements, which can show eighths of a character
+    Unicode
+}
+impl ChartStyle {
+    fn bar(&self, length: f64) -> String {
+        match self {
+            ChartStyle::Ascii => "#".repeat(length.round() as usize),
+            ChartStyle::Unicode => {
+                const PARTIALS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+                let eighths = (length * 8.0).round() as usize;
+                let mut bar = "█".repeat(eighths / 8);
+                if !eighths.is_multiple_of(8) {
+                    bar.push(PARTIALS[eighths % 8]); }
+                bar } } }
+}
+
+
+/// Settings for drawing `Histogram`s. The default is a `Frequency` chart in `Unicode` that's 40
+/// characters wide.
+#[derive(Clone, Debug)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub style: ChartStyle,
+    pub width: usize,
+}
+impl Chart {
+    pub fn new(kind: ChartKind, style: ChartStyle, width: usize) -> Self {
+        Self { kind, style, width } }
+
+    /// A `Frequency` chart in the given style with the default width
+    pub fn frequency(style: ChartStyle) -> Self {
+        Self::new(ChartKind::Frequency, style, 40) }
+
+    /// An `AtLeast` chart in the given style with the default width
+    pub fn at_least(style: ChartStyle) -> Self {
+        Self::new(ChartKind::AtLeast, style, 40) }
+}
+impl Default for Chart {
+    fn default() -> Self { Self::frequency(ChartStyle::Unicode) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Unit,
+        rollers::{Chart, ChartKind, ChartStyle, Histogram},
+        units::DNumUnit };
+
+    fn histogram() -> Histogram {
+        let unit: Rc<dyn Unit> = DNumUnit::new();
+        Histogram::from_values(unit, &[4, 2, 2, 4, 4, 4]) }
+
+    #[test]
+    fn counts_only_totals_that_came_up() {
+        let sut = histogram();
+        assert_eq!(sut.iter().map(|(total, count)| (*total, *count)).collect::<Vec<(i32, u32)>>(), vec![(2, 2), (4, 4)]);
+        assert_eq!(sut.count_of(3), 0);
+        assert!((sut.proportion_at_least(3) - 4.0 / 6.0).abs() < 1e-9); }
+
+    #[test]
+    fn adding_new_totals() {
+        let mut sut = histogram();
+        sut.add(0, 1);
+        sut.add(6, 2);
+        sut.add(4, 1);
+        assert_eq!(
+            sut.iter().map(|(total, count)| (*total, *count)).collect::<Vec<(i32, u32)>>(),
+            vec![(0, 1), (2, 2), (4, 5), (6, 2)]);
+        assert_eq!(sut.num_runs(), 10); }
+
+    #[test]
+    fn extreme_totals() {
+        let unit: Rc<dyn Unit> = DNumUnit::new();
+        let sut = Histogram::from_values(unit, &[i32::MIN, 3, i32::MAX, 3]);
+        assert_eq!((sut.min(), sut.max()), (Some(i32::MIN), Some(i32::MAX)));
+        assert_eq!(sut.iter().count(), 3);
+        assert_eq!(sut.proportion_at_least(i32::MAX), 0.25);
+        assert_eq!(sut.render(&Chart::new(ChartKind::Frequency, ChartStyle::Ascii, 2)).lines().count(), 3); }
+
+    #[test]
+    fn ascii_frequency_chart() {
+        let sut = histogram().render(&Chart::new(ChartKind::Frequency, ChartStyle::Ascii, 4));
+        assert_eq!(sut, "2 | ##    33.3%\n4 | ####  66.7%"); }
+
+    #[test]
+    fn unicode_at_least_chart() {
+        let sut = histogram().render(&Chart::new(ChartKind::AtLeast, ChartStyle::Unicode, 3));
+        assert_eq!(sut, "2+ | ███ 100.0%\n4+ | ██   66.7%"); }
+}
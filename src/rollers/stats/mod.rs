@@ -1,5 +1,21 @@
 mod calculate;
+mod compare;
+mod convergence;
+pub(crate) mod export;
+mod histogram;
+mod multi;
+mod query;
+mod sweep;
+mod tiers;
 use self::calculate::*;
+pub use self::{
+    compare::*,
+    convergence::*,
+    histogram::*,
+    multi::*,
+    query::Condition,
+    sweep::*,
+    tiers::* };
 
 use std::{
     fmt::{Display, Error, Formatter},
@@ -13,8 +29,8 @@ use crate::{
 
 /// `StatsRoller` is used to find out the statistics of a
 /// roll. It'll perform the given `Roller`'s roll a number of times
-/// given, then provide the average, median, mode, and standard 
-/// deviation for each `Unit`. You can also extract the
+/// given, then provide the average, median, mode, standard 
+/// deviation, and more for each `Unit`. You can also extract the
 /// results of each individual roll and calculate any other desired
 /// statistics based on that, such as graphing the results out somehow.
 ///
@@ -23,20 +39,57 @@ use crate::{
 /// `StatisticsRoll`, `average()`, `median()`, `mode()`, and 
 /// `std_deviation()`, which will return `Stat`s.
 /// 
+/// If you'd rather see the shape of the results, create it with `with_chart()` (or add one later
+/// with `charted()`), and `final_result()` will also draw a `Histogram` chart for each `Unit`.
+///
+/// Normally, every roll is kept so you can look back through them, but for huge numbers of runs,
+/// that takes up far too much memory. Turning on `streaming()` mode throws each roll away as soon
+/// as its totals have been counted, so memory use stays the same no matter how many runs there
+/// are. All of the stats are still available; only the individual rolls aren't.
+///
+/// If you don't know how many runs are enough, give it a `Convergence` with `until_converged()`.
+/// It will then roll in batches of the given number of runs until the stats are as precise as
+/// you asked for, and report how precise they got in `final_result()` and `precision()`.
+///
 /// Unfortunately, since `Unit`s work with integer numbers and the
 /// stats use floating point numbers (though, median and mode don't NEED to;
 /// they're simply kept consistent with the others that do), we can't convert
 /// the numbers into the `Unit`'s output. It will simply have the
 /// `Unit` and the value printed side-by-side.
+#[derive(Clone)]
 pub struct StatsRoller {
     runs: u32,
     roller: Rc<dyn SubRoller>,
+    chart: Option<Chart>,
+    streaming: bool,
+    convergence: Option<Convergence>,
 }
 impl StatsRoller {
     /// Creates a new `StatsRoller` using the given roller and a number of times to run it in order
     /// to generate the statistics
     pub fn new(roller: Rc<dyn SubRoller>, num_runs: NonZero<u32>) -> Rc<Self> {
-        Rc::new(Self { runs: num_runs.get(), roller }) }
+        Rc::new(Self { runs: num_runs.get(), roller, chart: None, streaming: false, convergence: None }) }
+
+    /// Same as `new()`, except the `final_result()` of its rolls also includes the given `Chart`
+    /// of each `Unit`'s `Histogram`
+    pub fn with_chart(roller: Rc<dyn SubRoller>, num_runs: NonZero<u32>, chart: Chart) -> Rc<Self> {
+        Self::new(roller, num_runs).charted(chart) }
+
+    /// Makes the `final_result()` of its rolls include the given `Chart` of each `Unit`'s
+    /// `Histogram`
+    pub fn charted(self: Rc<Self>, chart: Chart) -> Rc<Self> {
+        Rc::new(Self { chart: Some(chart), ..Rc::unwrap_or_clone(self) }) }
+
+    /// Makes its rolls throw away each run once its totals are counted, so that memory use doesn't
+    /// grow with the number of runs. `rolls()` will be empty on the resulting `StatisticsRoll`s.
+    pub fn streaming(self: Rc<Self>) -> Rc<Self> {
+        Rc::new(Self { streaming: true, ..Rc::unwrap_or_clone(self) }) }
+
+    /// Makes it keep rolling until the `Convergence`'s target is precise enough (or its
+    /// `max_runs` is reached), using the number of runs it was created with as the size of each
+    /// batch between checks.
+    pub fn until_converged(self: Rc<Self>, convergence: Convergence) -> Rc<Self> {
+        Rc::new(Self { convergence: Some(convergence), ..Rc::unwrap_or_clone(self) }) }
   
     /// Does the same thing as `roll()`, except it returns the roller as a statically-typed
     /// `StatisticsRoll` instead of a `dyn Roll`, giving access to its extra methods
@@ -46,14 +99,40 @@ impl StatsRoller {
     /// Does the same thing as `roll_with()`, except it returns the roller as a statically-typed
     /// `StatisticsRoll` instead of a `dyn Roll`, giving access to its extra methods
     pub fn statistics_roll_with(self: Rc<Self>, rng: Rng) -> Box<StatisticsRoll> {
-        StatisticsRoll::new(
-            (0..self.runs)
-            .map(|_| self.roller.clone().inner_roll_with(rng.clone()))
-            .collect()) }
+        let mut builder = CollectedStatsBuilder::new();
+        let mut rolls = Vec::new();
+        let mut roll_batch = |builder: &mut CollectedStatsBuilder, num_runs: u32| {
+            for _ in 0..num_runs {
+                let roll = self.roller.clone().inner_roll_with(rng.clone());
+                builder.add_roll_values(roll.final_totals());
+                if !self.streaming {
+                    rolls.push(roll); } } };
+        let precision = match &self.convergence {
+            None => {
+                roll_batch(&mut builder, self.runs);
+                None },
+            Some(convergence) => loop {
+                let remaining = convergence.max_runs.get() - builder.num_runs();
+                roll_batch(&mut builder, self.runs.min(remaining));
+                let precision = convergence.precision(&builder);
+                if precision.converged || builder.num_runs() >= convergence.max_runs.get() {
+                    break Some(precision) } } };
+        let num_runs = builder.num_runs();
+        let collected_stats = if self.streaming { builder.build_streamed() } else { builder.build() };
+        Box::new(StatisticsRoll {
+            rolls,
+            num_runs,
+            collected_stats,
+            chart: self.chart.clone(),
+            precision }) }
 }
 impl Roller for StatsRoller {
     fn description(&self) -> String {
-        format!("Runs '{}' {} times", self.roller.description(), self.runs) }
+        match &self.convergence {
+            Some(convergence) => format!(
+                "Runs '{}' in batches of {} until the standard error of {} is at most {} (up to {} times)",
+                self.roller.description(), self.runs, convergence.target, convergence.tolerance, convergence.max_runs),
+            None => format!("Runs '{}' {} times", self.roller.description(), self.runs) } }
     
     fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
         self.statistics_roll_with(rng) }
@@ -64,19 +143,21 @@ impl Roller for StatsRoller {
 /// numerous rolls and calculates the statistics of them.
 pub struct StatisticsRoll {
     rolls: Vec<Box<dyn SubRoll>>,
-    collected_stats: CollectedStats
+    num_runs: u32,
+    collected_stats: CollectedStats,
+    chart: Option<Chart>,
+    precision: Option<Precision>
 }
 impl StatisticsRoll {
-    fn new(rolls: Vec<Box<dyn SubRoll>>) -> Box<Self> {
-        let collected_stats = StatisticsRoll::run_calcs(&rolls);
-        Box::new(Self{ rolls, collected_stats }) }
-    
-    fn run_calcs(rolls: &Vec<Box<dyn SubRoll>>) -> CollectedStats{
-        let roll_vals: Vec<Values> = rolls.iter()
-            .map(|roll| roll.totals())
-            .collect();
-        let builder = CollectedStatsBuilder::new(roll_vals);
-        builder.build() }
+    /// The individual rolls, in the order they were rolled. These aren't kept when the
+    /// `StatsRoller` is in `streaming()` mode, in which case this is empty.
+    pub fn rolls(&self) -> &[Box<dyn SubRoll>] { &self.rolls }
+
+    /// Returns all the collected stats
+    pub fn collected_stats(&self) -> &CollectedStats { &self.collected_stats }
+
+    /// How precise the stats got, if the `StatsRoller` was using a `Convergence`
+    pub fn precision(&self) -> Option<&Precision> { self.precision.as_ref() }
     
     /// Look up the statistics for the given `Unit`, if there are any
     pub fn stats_for(&self, unit: Rc<dyn Unit>) -> Option<&UnitStats> {
@@ -93,25 +174,116 @@ impl StatisticsRoll {
     
     /// Returns the standard deviation for each `Unit`
     pub fn std_deviations(&self) -> Stat { self.collected_stats.std_deviations() }
+
+    /// Returns the lowest total for each `Unit`
+    pub fn mins(&self) -> Stat { self.collected_stats.mins() }
+
+    /// Returns the highest total for each `Unit`
+    pub fn maxes(&self) -> Stat { self.collected_stats.maxes() }
+
+    /// Returns the given percentile (0 to 100) for each `Unit`
+    pub fn percentiles(&self, percent: f32) -> Stat { self.collected_stats.percentiles(percent) }
+
+    /// Returns the variance for each `Unit`
+    pub fn variances(&self) -> Stat { self.collected_stats.variances() }
+
+    /// Returns the skewness for each `Unit`
+    pub fn skewnesses(&self) -> Stat { self.collected_stats.skewnesses() }
+
+    /// Returns the standard error of the average for each `Unit`
+    pub fn std_errors(&self) -> Stat { self.collected_stats.std_errors() }
+
+    /// Returns the confidence interval of the average at the given confidence level (0 to 1) for
+    /// each `Unit`
+    pub fn confidence_intervals(&self, confidence: f32) -> Stat {
+        self.collected_stats.confidence_intervals(confidence) }
+
+    /// Returns the `Histogram` for each `Unit`
+    pub fn histograms(&self) -> Vec<Histogram> { self.collected_stats.histograms() }
+
+    /// Returns the fraction (0 to 1) of the runs where the `Unit`'s total was exactly the given one
+    pub fn probability_of(&self, unit: &Rc<dyn Unit>, total: i32) -> f64 {
+        self.collected_stats.probability_of(unit, total) }
+
+    /// Returns the fraction (0 to 1) of the runs where the `Unit`'s total was the given one or more
+    pub fn probability_at_least(&self, unit: &Rc<dyn Unit>, total: i32) -> f64 {
+        self.collected_stats.probability_at_least(unit, total) }
+
+    /// Returns the fraction (0 to 1) of the runs where the `Unit`'s total was the given one or less
+    pub fn probability_at_most(&self, unit: &Rc<dyn Unit>, total: i32) -> f64 {
+        self.collected_stats.probability_at_most(unit, total) }
+
+    /// Returns the fraction (0 to 1) of the runs whose totals met the `Condition`
+    pub fn probability(&self, condition: &Condition) -> f64 {
+        self.collected_stats.probability(condition) }
+
+    /// Draws the given `Chart` for each `Unit`, each one headed by the `Unit`'s name
+    pub fn charts(&self, chart: &Chart) -> String { self.collected_stats.charts(chart) }
+
+    /// Returns how often each tier came up for each `Unit` that has tiers
+    pub fn tier_distributions(&self) -> Vec<TierDistribution> {
+        self.collected_stats.tier_distributions() }
+
+    /// Writes the stats out as CSV, with a row for each `Unit`. See `CollectedStats::to_csv()`.
+    pub fn stats_to_csv(&self) -> String { self.collected_stats.to_csv() }
+
+    /// Writes the stats out as JSON, with an object for each `Unit`. See
+    /// `CollectedStats::to_json()`.
+    pub fn stats_to_json(&self) -> String { self.collected_stats.to_json() }
+
+    /// Writes the totals of every roll out as CSV, with a column for each `Unit` and a row for each
+    /// roll. There are no rolls to write in `streaming()` mode, so only the header is written.
+    pub fn samples_to_csv(&self) -> String { export::samples_to_csv(&self.totals()) }
+
+    /// Writes the totals of every roll out as a JSON array, with an object for each roll, keyed by
+    /// `Unit`. There are no rolls to write in `streaming()` mode, so the array is empty.
+    pub fn samples_to_json(&self) -> String { export::samples_to_json(&self.totals()) }
+
+    fn totals(&self) -> Vec<Values> {
+        self.rolls.iter()
+            .map(|roll| roll.final_totals())
+            .collect() }
 }
 impl Roll for StatisticsRoll {
     /// Simpy returns "Result of # rolls"
     fn intermediate_results(&self) -> String { 
-        format!("Result of {} rolls", self.rolls.len()) }
+        format!("Result of {} rolls", self.num_runs) }
     
     fn final_result(&self) -> String {
-        format!("{}:\n{}\n{}\n{}\n{}", 
+        let summary = format!("{}:\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}", 
                 self.intermediate_results(),
                 self.averages(),
                 self.medians(),
                 self.modes(), 
-                self.std_deviations() ) }
+                self.std_deviations(),
+                self.mins(),
+                self.maxes(),
+                self.variances(),
+                self.skewnesses(),
+                self.std_errors(),
+                self.confidence_intervals(0.95) );
+        let tier_distributions = self.tier_distributions();
+        let summary = if tier_distributions.is_empty() { summary } else {
+            let tiers = tier_distributions.iter()
+                .map(TierDistribution::to_string)
+                .collect::<String>();
+            format!("{}Tiers:\n{}", summary, tiers) };
+        let summary = match &self.precision {
+            Some(precision) => format!("{}{}\n", summary, precision),
+            None => summary };
+        match &self.chart {
+            Some(chart) => format!("{}\n{}", summary, self.charts(chart)),
+            None => summary } }
 }
 
 
-/// A collection of all the stats, grouped by `Unit` in `UnitStats`
+/// A collection of all the stats, grouped by `Unit` in `UnitStats`, along with how many times
+/// each distinct combination of totals came up, which is needed for asking questions that involve
+/// more than one `Unit`.
 pub struct CollectedStats {
-  stats: Vec<UnitStats>
+  stats: Vec<UnitStats>,
+  outcomes: Vec<(Values, u32)>,
+  num_runs: u32
 }
 impl CollectedStats {
     /// Returns the `UnitStats` for the given `Unit`
@@ -128,7 +300,8 @@ impl CollectedStats {
             values: self.stats.iter()
                 .map(|rstats| StatValue {
                    unit: rstats.unit.clone(),
-                    value: rstats.average })
+                    value: rstats.average,
+                    range_end: None })
                 .collect() } }
     
     /// Returns the median for each `Unit`            
@@ -138,7 +311,8 @@ impl CollectedStats {
             values: self.stats.iter()
                 .map(|rstats| StatValue {
                    unit: rstats.unit.clone(),
-                    value: rstats.median })
+                    value: rstats.median,
+                    range_end: None })
                 .collect() } }
     
     /// Returns the mode for each `Unit`
@@ -148,7 +322,8 @@ impl CollectedStats {
             values: self.stats.iter()
                 .map(|rstats| StatValue {
                    unit: rstats.unit.clone(),
-                    value: rstats.mode })
+                    value: rstats.mode,
+                    range_end: None })
                 .collect() } }
     
     /// Returns the standard deviation for each `Unit`
@@ -158,22 +333,134 @@ impl CollectedStats {
             values: self.stats.iter()
                 .map(|rstats| StatValue {
                    unit: rstats.unit.clone(),
-                    value: rstats.std_deviation })
+                    value: rstats.std_deviation,
+                    range_end: None })
+                .collect() } }
+
+    /// Returns the lowest total for each `Unit`
+    pub fn mins(&self) -> Stat {
+        self.stat_of(StatType::Min, |rstats| rstats.min as f32) }
+
+    /// Returns the highest total for each `Unit`
+    pub fn maxes(&self) -> Stat {
+        self.stat_of(StatType::Max, |rstats| rstats.max as f32) }
+
+    /// Returns the given percentile (0 to 100) for each `Unit`
+    pub fn percentiles(&self, percent: f32) -> Stat {
+        self.stat_of(StatType::Percentile(percent), |rstats| rstats.percentile(percent)) }
+
+    /// Returns the variance for each `Unit`
+    pub fn variances(&self) -> Stat {
+        self.stat_of(StatType::Variance, |rstats| rstats.variance) }
+
+    /// Returns the skewness for each `Unit`
+    pub fn skewnesses(&self) -> Stat {
+        self.stat_of(StatType::Skewness, |rstats| rstats.skewness) }
+
+    /// Returns the standard error of the average for each `Unit`
+    pub fn std_errors(&self) -> Stat {
+        self.stat_of(StatType::StdError, |rstats| rstats.std_error) }
+
+    /// Returns the confidence interval of the average at the given confidence level (0 to 1) for
+    /// each `Unit`. The `Stat`'s values are the low ends of the intervals; use `range_for()` or
+    /// `StatValue::range_end()` to get the high ends.
+    pub fn confidence_intervals(&self, confidence: f32) -> Stat {
+        Stat {
+            stat_type: StatType::ConfidenceInterval(confidence),
+            values: self.stats.iter()
+                .map(|rstats| {
+                    let (low, high) = rstats.confidence_interval(confidence);
+                    StatValue {
+                        unit: rstats.unit.clone(),
+                        value: low,
+                        range_end: Some(high) } })
                 .collect() } }
+
+    fn stat_of(&self, stat_type: StatType, value: impl Fn(&UnitStats) -> f32) -> Stat {
+        Stat {
+            stat_type,
+            values: self.stats.iter()
+                .map(|rstats| StatValue {
+                    unit: rstats.unit.clone(),
+                    value: value(rstats),
+                    range_end: None })
+                .collect() } }
+
+    /// Returns each distinct set of totals that came up, along with the number of runs that had
+    /// it, in no particular order
+    pub fn outcomes(&self) -> &[(Values, u32)] { &self.outcomes }
+
+    /// Returns the number of runs the stats were collected from
+    pub fn num_runs(&self) -> u32 { self.num_runs }
+
+    /// Returns the fraction (0 to 1) of the runs where the `Unit`'s total was exactly the given one
+    pub fn probability_of(&self, unit: &Rc<dyn Unit>, total: i32) -> f64 {
+        self.probability(&Condition::equals(unit, total)) }
+
+    /// Returns the fraction (0 to 1) of the runs where the `Unit`'s total was the given one or more
+    pub fn probability_at_least(&self, unit: &Rc<dyn Unit>, total: i32) -> f64 {
+        self.probability(&Condition::at_least(unit, total)) }
+
+    /// Returns the fraction (0 to 1) of the runs where the `Unit`'s total was the given one or less
+    pub fn probability_at_most(&self, unit: &Rc<dyn Unit>, total: i32) -> f64 {
+        self.probability(&Condition::at_most(unit, total)) }
+
+    /// Returns the fraction (0 to 1) of the runs whose totals met the `Condition`
+    pub fn probability(&self, condition: &Condition) -> f64 {
+        query::probability(&self.outcomes, condition) }
+
+    /// Returns the `Histogram` for each `Unit`
+    pub fn histograms(&self) -> Vec<Histogram> {
+        self.stats.iter()
+            .map(|rstats| rstats.histogram.clone())
+            .collect() }
+
+    /// Returns how often each tier came up for each `Unit` that has tiers
+    pub fn tier_distributions(&self) -> Vec<TierDistribution> {
+        self.stats.iter()
+            .filter_map(UnitStats::tier_distribution)
+            .collect() }
+
+    /// Draws the given `Chart` for each `Unit`, each one headed by the `Unit`'s name
+    pub fn charts(&self, chart: &Chart) -> String {
+        self.histograms().iter()
+            .map(|histogram| format!("{}:\n{}\n", histogram.unit, histogram.render(chart)))
+            .collect::<Vec<String>>()
+            .join("\n") }
 }
 
 
 /// Enum to distinguish between the different kinds of statistics gathered
 pub enum StatType {
-    Average, Median, Mode, StdDeviation
+    Average, Median, Mode, StdDeviation, Min, Max, Variance, Skewness, StdError,
+    /// Holds which percentile (0 to 100)
+    Percentile(f32),
+    /// Holds the confidence level (0 to 1)
+    ConfidenceInterval(f32)
 }
 impl Display for StatType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self { 
             StatType::Average => f.write_str("Average"),
-            StatType::Median => f.write_str("Media"),
+            StatType::Median => f.write_str("Median"),
             StatType::Mode => f.write_str("Mode"),
-            StatType::StdDeviation => f.write_str("Standard Deviation")
+            StatType::StdDeviation => f.write_str("Standard Deviation"),
+            StatType::Min => f.write_str("Minimum"),
+            StatType::Max => f.write_str("Maximum"),
+            StatType::Variance => f.write_str("Variance"),
+            StatType::Skewness => f.write_str("Skewness"),
+            StatType::StdError => f.write_str("Standard Error"),
+            StatType::Percentile(percent) => {
+                let suffix = match (*percent as u32 % 100, *percent as u32 % 10) {
+                    _ if percent.fract() != 0.0 => "th",
+                    (11..=13, _) => "th",
+                    (_, 1) => "st",
+                    (_, 2) => "nd",
+                    (_, 3) => "rd",
+                    _ => "th" };
+                f.write_fmt(format_args!("{}{} Percentile", percent, suffix)) },
+            StatType::ConfidenceInterval(confidence) =>
+                f.write_fmt(format_args!("{}% Confidence Interval", confidence * 100.0))
         }
     }
 }
@@ -192,6 +479,13 @@ impl Stat {
             .filter(|stat_val| stat_val.has_same_unit(unit.clone()))
             .map(|stat_val| stat_val.value)
             .next() }
+
+    /// Look up the range of this `Stat`'s `StatType` for the given `Unit`, if there is one. Only
+    /// ranged stats, like confidence intervals, have one.
+    pub fn range_for(&self, unit: Rc<dyn Unit>) -> Option<(f32, f32)> {
+        self.values.iter()
+            .filter(|stat_val| stat_val.has_same_unit(unit.clone()))
+            .find_map(|stat_val| stat_val.range_end.map(|end| (stat_val.value, end))) }
     
     /// Iterator to cycle through the stats by `Unit`
     pub fn iter(&self) -> impl Iterator<Item=&StatValue> { self.values.iter() }
@@ -219,28 +513,69 @@ impl Display for Stat {
 pub struct StatValue {
     unit: Rc<dyn Unit>,
     value: f32,
+    range_end: Option<f32>,
 }
 impl StatValue {
     pub fn has_same_unit(&self, unit: Rc<dyn Unit>) -> bool {
         self.unit.deref() == unit.deref() }
+
+    /// The value of the stat, or the low end of it if it's a range
+    pub fn value(&self) -> f32 { self.value }
+
+    /// The high end of the stat if it's a range, such as a confidence interval
+    pub fn range_end(&self) -> Option<f32> { self.range_end }
 }
 impl Display for StatValue {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}: {:.2}", self.unit, self.value)) }
+        match self.range_end {
+            Some(end) => f.write_fmt(format_args!("{}: {:.2} to {:.2}", self.unit, self.value, end)),
+            None => f.write_fmt(format_args!("{}: {:.2}", self.unit, self.value)) } }
 }
 
 
-/// Holds all of the stats for a certain `Unit` as well as all of the values that were used to
-/// calculate those stats (in no particular order).
+/// Holds all of the stats for a certain `Unit` as well as a `Histogram` of all the values that were
+/// used to calculate those stats.
 pub struct UnitStats {
     pub unit: Rc<dyn Unit>,
+    /// Every value that was used to calculate the stats, sorted from lowest to highest. It's empty
+    /// for streaming `StatsRoller`s, since keeping them all is what streaming avoids.
+    #[deprecated(note = "use `histogram` or `values()`, which work for streaming `StatsRoller`s too")]
     pub values: Vec<i32>,
+    pub histogram: Histogram,
     pub average: f32,
     pub median: f32,
     pub mode: f32,
-    pub std_deviation: f32
+    pub std_deviation: f32,
+    pub min: i32,
+    pub max: i32,
+    pub variance: f32,
+    pub skewness: f32,
+    /// Standard error of the average
+    pub std_error: f32
 }
 impl UnitStats {
     pub fn has_same_unit(&self, unit: Rc<dyn Unit>) -> bool {
         self.unit.deref() == unit.deref() }
+
+    /// Finds the given percentile (0 to 100) of the values, interpolating between the closest two
+    /// if it falls between them. The 50th percentile is the median.
+    pub fn percentile(&self, percent: f32) -> f32 {
+        calculate::percentile(&self.histogram, percent) }
+
+    /// The range that the true average is expected to be in at the given confidence level (0 to
+    /// 1), i.e. 0.95 for a 95% confidence interval. With the numbers of runs statistics are usually
+    /// done with, the normal approximation used here is plenty accurate.
+    pub fn confidence_interval(&self, confidence: f32) -> (f32, f32) {
+        let margin = calculate::z_score(confidence) * self.std_error;
+        (self.average - margin, self.average + margin) }
+
+    /// How often each of the `Unit`'s tiers came up, if it has tiers
+    pub fn tier_distribution(&self) -> Option<TierDistribution> {
+        TierDistribution::from_histogram(&self.histogram) }
+
+    /// Lists out every value that was used to calculate the stats, sorted from lowest to highest
+    pub fn values(&self) -> Vec<i32> {
+        self.histogram.iter()
+            .flat_map(|(total, count)| std::iter::repeat_n(*total, *count as usize))
+            .collect() }
 }
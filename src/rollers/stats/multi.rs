@@ -0,0 +1,220 @@
+use std::{
+    num::NonZero,
+    rc::Rc };
+use crate::{
+    Name, Unit, Values,
+    random::{Rng, default_rng},
+    rollers::{CollectedStats, Condition, MultiRoller, Roll, Roller, SubRoller, stats::calculate::CollectedStatsBuilder} };
+
+
+/// `MultiStatsRoller` is the statistics version of `MultiRoller`. It rolls every one of its named
+/// rollers together in each run, such as an attack and its damage, and gathers the stats of each
+/// of them separately. Since the rolls of each run are kept together, it can also tell you how the
+/// entries relate to each other, such as the average damage when the attack hits.
+///
+/// `MultiRoller` can hold plain `Roller`s, which don't give totals, so the entries here need to be
+/// `SubRoller`s instead. Use `from_multi()` for a `MultiRoller` whose rollers were all added as
+/// `SubRoller`s.
+pub struct MultiStatsRoller {
+    runs: NonZero<u32>,
+    entries: Vec<(Name, Rc<dyn SubRoller>)>,
+}
+impl MultiStatsRoller {
+    /// Creates a `MultiStatsRoller` that rolls all of the named rollers `num_runs` times
+    pub fn new(entries: Vec<(Name, Rc<dyn SubRoller>)>, num_runs: NonZero<u32>) -> Rc<Self> {
+        Rc::new(Self { runs: num_runs, entries }) }
+
+    /// Creates a `MultiStatsRoller` that rolls all of the `MultiRoller`'s rollers `num_runs` times.
+    /// Returns None if any of them isn't a `SubRoller` (see `NamedRoller::new_sub()`).
+    pub fn from_multi(multi: &MultiRoller, num_runs: NonZero<u32>) -> Option<Rc<Self>> {
+        let entries = multi.rollers().iter()
+            .map(|named| named.sub_roller().map(|roller| (named.name().clone(), roller.clone())))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::new(entries, num_runs)) }
+
+    /// Does the same thing as `roll()`, except it returns the roll as a statically-typed
+    /// `MultiStatsRoll` instead of a `dyn Roll`, giving access to its extra methods
+    pub fn multi_stats_roll(self: Rc<Self>) -> Box<MultiStatsRoll> {
+        self.multi_stats_roll_with(default_rng()) }
+
+    /// Does the same thing as `roll_with()`, except it returns the roll as a statically-typed
+    /// `MultiStatsRoll` instead of a `dyn Roll`, giving access to its extra methods
+    pub fn multi_stats_roll_with(self: Rc<Self>, rng: Rng) -> Box<MultiStatsRoll> {
+        let mut builders: Vec<CollectedStatsBuilder> = self.entries.iter()
+            .map(|_| CollectedStatsBuilder::new())
+            .collect();
+        let runs: Vec<Vec<Values>> = (0..self.runs.get())
+            .map(|_| self.entries.iter()
+                .zip(builders.iter_mut())
+                .map(|((_, roller), builder)| {
+                    let totals = roller.clone().inner_roll_with(rng.clone()).final_totals();
+                    builder.add_roll_values(totals.clone());
+                    totals })
+                .collect())
+            .collect();
+        Box::new(MultiStatsRoll {
+            entries: self.entries.iter()
+                .zip(builders)
+                .map(|((name, _), builder)| (name.clone(), builder.build()))
+                .collect(),
+            runs }) }
+}
+impl Roller for MultiStatsRoller {
+    fn description(&self) -> String {
+        let rollers = self.entries.iter()
+            .map(|(name, roller)| format!("{}: {}", name, roller.description()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("Runs {} together {} times", rollers, self.runs) }
+
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        self.multi_stats_roll_with(rng) }
+}
+
+
+/// `MultiStatsRoll` is the `Roll` created by `MultiStatsRoller`. Entries are referred to by their
+/// index, in the order they were given to the roller; asking about an index past the last entry
+/// gives None.
+pub struct MultiStatsRoll {
+    entries: Vec<(Name, CollectedStats)>,
+    runs: Vec<Vec<Values>>,
+}
+impl MultiStatsRoll {
+    /// Iterates through the name and stats of each entry
+    pub fn entries(&self) -> impl Iterator<Item=(&Name, &CollectedStats)> {
+        self.entries.iter().map(|(name, stats)| (name, stats)) }
+
+    /// Returns the stats of the entry with the given name (ignoring case), if there is one
+    pub fn stats_for(&self, name: &str) -> Option<&CollectedStats> {
+        self.entries.iter()
+            .find(|(entry_name, _)| entry_name.matches(name))
+            .map(|(_, stats)| stats) }
+
+    /// The stats of the entry at `target`, using only the runs where the entry at `given` met the
+    /// `Condition`. For example, the damage of only the attacks that hit. Returns None if no runs
+    /// met the `Condition`, or if either index is past the last entry.
+    pub fn stats_given(&self, target: usize, given: usize, condition: &Condition) -> Option<CollectedStats> {
+        if !self.has_entries(&[target, given]) { return None }
+        let mut builder = CollectedStatsBuilder::new();
+        for run in self.runs.iter().filter(|run| condition.is_met_by(&run[given])) {
+            builder.add_roll_values(run[target].clone()); }
+        if builder.num_runs() == 0 { None }
+        else { Some(builder.build()) } }
+
+    /// The average total of the `Unit` for the entry at `target` over all of the runs, counting
+    /// the runs where the entry at `given` didn't meet the `Condition` as 0. For example, the
+    /// expected damage of an attack, where a miss does no damage. Returns None if either index is
+    /// past the last entry.
+    pub fn expected_given(&self, target: usize, unit: &Rc<dyn Unit>, given: usize, condition: &Condition) -> Option<f32> {
+        if !self.has_entries(&[target, given]) { return None }
+        if self.runs.is_empty() { return Some(0.0) }
+        let sum: f64 = self.runs.iter()
+            .filter(|run| condition.is_met_by(&run[given]))
+            .map(|run| run[target].total_for(unit) as f64)
+            .sum();
+        Some((sum / self.runs.len() as f64) as f32) }
+
+    /// The Pearson correlation (-1 to 1) between the total of one entry's `Unit` and another
+    /// entry's `Unit` across the runs. 0 means they don't go up and down together at all, such as
+    /// for entries rolled independently of each other. If either total never changes, the
+    /// correlation doesn't mean anything, and this returns 0. Returns None if either index is past
+    /// the last entry.
+    pub fn correlation(&self, first: (usize, &Rc<dyn Unit>), second: (usize, &Rc<dyn Unit>)) -> Option<f64> {
+        if !self.has_entries(&[first.0, second.0]) { return None }
+        let totals = |(idx, unit): (usize, &Rc<dyn Unit>)| self.runs.iter()
+            .map(|run| run[idx].total_for(unit) as f64)
+            .collect::<Vec<f64>>();
+        let (xs, ys) = (totals(first), totals(second));
+        let count = xs.len() as f64;
+        if count == 0.0 { return Some(0.0) }
+        let (x_avg, y_avg) = (xs.iter().sum::<f64>() / count, ys.iter().sum::<f64>() / count);
+        let (mut covariance, mut x_var, mut y_var) = (0.0, 0.0, 0.0);
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            covariance += (x - x_avg) * (y - y_avg);
+            x_var += (x - x_avg).powi(2);
+            y_var += (y - y_avg).powi(2); }
+        if x_var == 0.0 || y_var == 0.0 { Some(0.0) }
+        else { Some(covariance / (x_var * y_var).sqrt()) } }
+
+    /// Whether every index is one of the entries
+    fn has_entries(&self, indices: &[usize]) -> bool {
+        indices.iter().all(|idx| *idx < self.entries.len()) }
+
+    fn units_of(&self, idx: usize) -> Vec<Rc<dyn Unit>> {
+        self.entries[idx].1.stats.iter()
+            .map(|rstats| rstats.unit.clone())
+            .collect() }
+}
+impl Roll for MultiStatsRoll {
+    /// Simply returns "Result of # runs of # rollers"
+    fn intermediate_results(&self) -> String {
+        format!("Result of {} runs of {} rollers", self.runs.len(), self.entries.len()) }
+
+    fn final_result(&self) -> String {
+        let mut out = format!("{}:\n", self.intermediate_results());
+        for (name, stats) in self.entries.iter() {
+            out += &format!("{}:\n{}{}", name, stats.averages(), stats.std_deviations()); }
+        let mut correlations = String::new();
+        for first in 0..self.entries.len() {
+            for second in first + 1..self.entries.len() {
+                for first_unit in self.units_of(first) {
+                    for second_unit in self.units_of(second) {
+                        correlations += &format!(
+                            "{} {} / {} {}: {:.3}\n",
+                            self.entries[first].0, first_unit,
+                            self.entries[second].0, second_unit,
+                            self.correlation((first, &first_unit), (second, &second_unit)).unwrap()); } } } }
+        if !correlations.is_empty() {
+            out += &format!("Correlations:\n{}", correlations); }
+        out }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZero, rc::Rc};
+    use crate::{
+        Name,
+        premade::number,
+        random::with_seed,
+        rollers::{Condition, MultiRoller, MultiStatsRoller, NamedRoller, Roller, SubRoller} };
+
+    #[test]
+    fn damage_given_attack_hits() {
+        let (unit, dice) = number::build();
+        let attack: Rc<dyn SubRoller> = dice[7].clone();
+        let damage: Rc<dyn SubRoller> = dice[3].clone();
+        let sut = MultiStatsRoller::new(
+                vec![(Name::new("Attack").unwrap(), attack), (Name::new("Damage").unwrap(), damage)],
+                NonZero::new(20_000).unwrap())
+            .multi_stats_roll_with(with_seed(12));
+
+        let hits = Condition::at_least(&unit, 11);
+        let given_hit = sut.stats_given(1, 0, &hits).unwrap();
+        assert!((given_hit.num_runs() as f32 / 20_000.0 - 0.5).abs() < 0.02);
+        assert!((given_hit.for_unit(unit.clone()).unwrap().average - 3.5).abs() < 0.1);
+        // misses do no damage, so it's about half of 3.5
+        assert!((sut.expected_given(1, &unit, 0, &hits).unwrap() - 1.75).abs() < 0.1);
+        assert!(sut.correlation((0, &unit), (1, &unit)).unwrap().abs() < 0.05);
+        assert!((sut.correlation((0, &unit), (0, &unit)).unwrap() - 1.0).abs() < 1e-9);
+        assert!(sut.stats_given(1, 0, &Condition::at_least(&unit, 21)).is_none());
+
+        // there are only 2 entries
+        assert!(sut.stats_given(2, 0, &hits).is_none());
+        assert!(sut.expected_given(1, &unit, 2, &hits).is_none());
+        assert!(sut.correlation((0, &unit), (2, &unit)).is_none()); }
+
+    #[test]
+    fn from_multi_roller() {
+        let (unit, dice) = number::build();
+        let multi = MultiRoller::new(vec![
+            NamedRoller::new_sub(Name::new("Attack").unwrap(), dice[7].clone()),
+            NamedRoller::new_sub(Name::new("Damage").unwrap(), dice[3].clone())]);
+        let sut = MultiStatsRoller::from_multi(&multi, NonZero::new(10_000).unwrap()).unwrap();
+        assert_eq!(sut.description(), "Runs Attack: d20, Damage: d6 together 10000 times");
+        let roll = sut.multi_stats_roll_with(with_seed(12));
+        assert!((roll.expected_given(1, &unit, 0, &Condition::at_least(&unit, 11)).unwrap() - 1.75).abs() < 0.1);
+
+        let plain = MultiRoller::new_numbered([dice[7].clone() as Rc<dyn Roller>]);
+        assert!(MultiStatsRoller::from_multi(&plain, NonZero::new(10).unwrap()).is_none()); }
+}
@@ -0,0 +1,143 @@
+use std::{
+    ops::Not,
+    rc::Rc };
+use crate::{Unit, Values};
+
+
+/// A yes-or-no question about the totals of a single run, used to ask `CollectedStats` how often
+/// something happened. The simple conditions compare one `Unit`'s total against a number, where a
+/// run without a `Value` for the `Unit` counts as its `default_value()` (usually 0). Those can then
+/// be combined with `and()`, `or()`, and `!` to ask about several `Unit`s at once, such as "at
+/// least 1 Success and at least 2 Advantage":
+///
+/// ```text
+/// Condition::at_least(&success, 1).and(Condition::at_least(&advantage, 2))
+/// ```
+#[derive(Clone, Debug)]
+pub enum Condition {
+    Equals(Rc<dyn Unit>, i32),
+    AtLeast(Rc<dyn Unit>, i32),
+    AtMost(Rc<dyn Unit>, i32),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+}
+impl Condition {
+    /// The `Unit`'s total is exactly the given number
+    pub fn equals(unit: &Rc<dyn Unit>, total: i32) -> Self {
+        Condition::Equals(unit.clone(), total) }
+
+    /// The `Unit`'s total is the given number or higher
+    pub fn at_least(unit: &Rc<dyn Unit>, total: i32) -> Self {
+        Condition::AtLeast(unit.clone(), total) }
+
+    /// The `Unit`'s total is the given number or lower
+    pub fn at_most(unit: &Rc<dyn Unit>, total: i32) -> Self {
+        Condition::AtMost(unit.clone(), total) }
+
+    /// The `Unit`'s total is higher than the given number. Nothing is higher than `i32::MAX`, so
+    /// that's never met.
+    pub fn more_than(unit: &Rc<dyn Unit>, total: i32) -> Self {
+        match total.checked_add(1) {
+            Some(total) => Condition::AtLeast(unit.clone(), total),
+            None => Condition::never() } }
+
+    /// The `Unit`'s total is lower than the given number. Nothing is lower than `i32::MIN`, so
+    /// that's never met.
+    pub fn less_than(unit: &Rc<dyn Unit>, total: i32) -> Self {
+        match total.checked_sub(1) {
+            Some(total) => Condition::AtMost(unit.clone(), total),
+            None => Condition::never() } }
+
+    /// A condition that no run meets, since it asks for any of an empty list of conditions
+    fn never() -> Self { Condition::Any(Vec::new()) }
+
+    /// Both this and the other condition are met
+    pub fn and(self, other: Condition) -> Self {
+        match self {
+            Condition::All(mut conditions) => {
+                conditions.push(other);
+                Condition::All(conditions) },
+            _ => Condition::All(vec![self, other]) } }
+
+    /// Either this or the other condition (or both) are met
+    pub fn or(self, other: Condition) -> Self {
+        match self {
+            Condition::Any(mut conditions) => {
+                conditions.push(other);
+                Condition::Any(conditions) },
+            _ => Condition::Any(vec![self, other]) } }
+
+    /// Checks whether the totals of a run meet this condition
+    pub fn is_met_by(&self, totals: &Values) -> bool {
+        match self {
+            Condition::Equals(unit, total) => totals.total_for(unit) == *total,
+            Condition::AtLeast(unit, total) => totals.total_for(unit) >= *total,
+            Condition::AtMost(unit, total) => totals.total_for(unit) <= *total,
+            Condition::All(conditions) => conditions.iter().all(|cond| cond.is_met_by(totals)),
+            Condition::Any(conditions) => conditions.iter().any(|cond| cond.is_met_by(totals)),
+            Condition::Not(condition) => !condition.is_met_by(totals) } }
+}
+impl Not for Condition {
+    type Output = Self;
+
+    /// The condition isn't met
+    fn not(self) -> Self::Output {
+        Condition::Not(Box::new(self)) }
+}
+
+
+/// Fraction (0 to 1) of the runs that meet the condition, given each distinct set of totals and
+/// how many runs came up with it
+pub(super) fn probability(outcomes: &[(Values, u32)], condition: &Condition) -> f64 {
+    let num_runs: u64 = outcomes.iter().map(|(_, count)| *count as u64).sum();
+    if num_runs == 0 { return 0.0 }
+    let num_met: u64 = outcomes.iter()
+        .filter(|(totals, _)| condition.is_met_by(totals))
+        .map(|(_, count)| *count as u64)
+        .sum();
+    num_met as f64 / num_runs as f64 }
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit, Value, Values,
+        rollers::{Condition, stats::query::probability},
+        units::BasicUnit };
+
+    fn unit(name: &str) -> Rc<dyn Unit> {
+        BasicUnit::new(Name::new(name).unwrap(), "{}", false) }
+
+    #[test]
+    fn compound_conditions() {
+        let success = unit("Success");
+        let advantage = unit("Advantage");
+        let run = |succ, adv| Values::from(vec![
+            Value { unit: success.clone(), value: succ },
+            Value { unit: advantage.clone(), value: adv }]);
+        // The last run has no Advantage at all, which counts as 0
+        let runs = vec![
+            (run(1, 2), 1), (run(0, 3), 1), (run(2, -1), 1), (run(3, 2), 1),
+            (Values::from(Value { unit: success.clone(), value: 1 }), 1)];
+
+        let both = Condition::more_than(&success, 0).and(Condition::at_least(&advantage, 2));
+        assert_eq!(probability(&runs, &both), 0.4);
+        let either = Condition::more_than(&success, 0).or(Condition::at_least(&advantage, 2));
+        assert_eq!(probability(&runs, &either), 1.0);
+        assert_eq!(probability(&runs, &Condition::equals(&advantage, 0)), 0.2);
+        assert_eq!(probability(&runs, &!Condition::at_most(&advantage, 0)), 0.6); }
+
+    #[test]
+    fn nothing_is_past_the_extremes() {
+        let success = unit("Success");
+        let runs = vec![
+            (Values::from(Value { unit: success.clone(), value: i32::MAX }), 1),
+            (Values::from(Value { unit: success.clone(), value: i32::MIN }), 1)];
+
+        assert_eq!(probability(&runs, &Condition::more_than(&success, i32::MAX)), 0.0);
+        assert_eq!(probability(&runs, &Condition::less_than(&success, i32::MIN)), 0.0);
+        assert_eq!(probability(&runs, &Condition::more_than(&success, i32::MAX - 1)), 0.5);
+        assert_eq!(probability(&runs, &Condition::less_than(&success, i32::MIN + 1)), 0.5); }
+}
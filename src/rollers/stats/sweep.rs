@@ -0,0 +1,192 @@
+use std::{
+    fmt::{Display, Formatter},
+    num::NonZero,
+    ops::RangeInclusive,
+    rc::Rc };
+use crate::{
+    Name,
+    random::{Rng, default_rng},
+    rollers::{StatisticsRoll, StatsRoller, SubRoller},
+    serialize::csv_field };
+
+
+/// One of the numbers that a `Sweep` changes, such as the size of a pool or the difficulty of a
+/// check, along with the name it's given in the table's headers
+#[derive(Clone, Debug)]
+pub struct SweepAxis {
+    pub name: Name,
+    pub values: RangeInclusive<i32>,
+}
+impl SweepAxis {
+    pub fn new(name: Name, values: RangeInclusive<i32>) -> Self {
+        Self { name, values } }
+}
+
+
+/// `Sweep` builds a table of stats over a range of one or two numbers, such as the chance of
+/// success by pool size 1 to 8 (the rows) against difficulty 1 to 5 (the columns). For each cell,
+/// a factory creates the roller from the row's and column's numbers, a `StatsRoller` rolls it,
+/// and a measure picks the number to show from the resulting `StatisticsRoll`.
+///
+/// The measure gets the numbers too, so if a number only changes what's measured (like the target
+/// of a check), the factory can just ignore it. Every cell is rolled with the same seed, so the
+/// differences between cells come from the numbers rather than from luck.
+#[derive(Clone, Debug)]
+pub struct Sweep {
+    rows: SweepAxis,
+    columns: Option<SweepAxis>,
+    runs: NonZero<u32>,
+}
+impl Sweep {
+    /// Creates a `Sweep` over a single number, where each cell is rolled `num_runs` times
+    pub fn new(rows: SweepAxis, num_runs: NonZero<u32>) -> Self {
+        Self { rows, columns: None, runs: num_runs } }
+
+    /// Adds a second number to sweep over, giving the table a column for each of its values
+    pub fn by(self, columns: SweepAxis) -> Self {
+        Self { columns: Some(columns), ..self } }
+
+    /// Fills the table using the default `Rng`. Without columns, the second number given to the
+    /// factory and measure is always 0.
+    pub fn table(
+        &self,
+        factory: impl Fn(i32, i32) -> Rc<dyn SubRoller>,
+        measure: impl Fn(&StatisticsRoll, i32, i32) -> f64
+    ) -> SweepTable {
+        self.table_with(default_rng(), factory, measure) }
+
+    /// Fills the table using the given `Rng`. Without columns, the second number given to the
+    /// factory and measure is always 0.
+    pub fn table_with(
+        &self,
+        mut rng: Rng,
+        factory: impl Fn(i32, i32) -> Rc<dyn SubRoller>,
+        measure: impl Fn(&StatisticsRoll, i32, i32) -> f64
+    ) -> SweepTable {
+        let seed = rng.next();
+        let columns = self.column_values();
+        let cells = self.rows.values.clone()
+            .map(|row| columns.iter()
+                .map(|&column| {
+                    let stats = StatsRoller::new(factory(row, column), self.runs)
+                        .streaming()
+                        .statistics_roll_with(Rng::from_seed(seed));
+                    measure(&stats, row, column) })
+                .collect())
+            .collect();
+        SweepTable { rows: self.rows.clone(), columns: self.columns.clone(), cells } }
+
+    fn column_values(&self) -> Vec<i32> {
+        match &self.columns {
+            Some(columns) => columns.values.clone().collect(),
+            None => vec![0] } }
+}
+
+
+/// The results of a `Sweep`, with a row for each value of the first number and a column for each
+/// value of the second (or a single column if there isn't a second)
+pub struct SweepTable {
+    rows: SweepAxis,
+    columns: Option<SweepAxis>,
+    cells: Vec<Vec<f64>>,
+}
+impl SweepTable {
+    /// The measured number for the given row and column values. Without columns, use 0 as the
+    /// column.
+    pub fn get(&self, row: i32, column: i32) -> Option<f64> {
+        let row_idx = self.rows.values.clone().position(|value| value == row)?;
+        let column_idx = match &self.columns {
+            Some(columns) => columns.values.clone().position(|value| value == column)?,
+            None if column == 0 => 0,
+            None => return None };
+        Some(self.cells[row_idx][column_idx]) }
+
+    /// Iterates through each row's value along with its measured numbers, one per column
+    pub fn iter(&self) -> impl Iterator<Item=(i32, &[f64])> {
+        self.rows.values.clone()
+            .zip(self.cells.iter().map(Vec::as_slice)) }
+
+    /// Writes the table as CSV. The first column holds the rows' values, headed by the rows' name.
+    /// The rest are headed by "<column name> <value>", or just "Value" without columns.
+    pub fn to_csv(&self) -> String {
+        let header = std::iter::once(self.rows.name.to_string())
+            .chain(self.column_headers())
+            .map(|header| csv_field(&header))
+            .collect::<Vec<String>>()
+            .join(",");
+        let rows = self.iter()
+            .map(|(row, cells)| std::iter::once(row.to_string())
+                .chain(cells.iter().map(f64::to_string))
+                .collect::<Vec<String>>()
+                .join(","));
+        std::iter::once(header).chain(rows)
+            .map(|line| line + "\n")
+            .collect() }
+
+    fn column_headers(&self) -> Vec<String> {
+        match &self.columns {
+            Some(columns) => columns.values.clone()
+                .map(|value| format!("{} {}", columns.name, value))
+                .collect(),
+            None => vec!["Value".to_string()] } }
+}
+impl Display for SweepTable {
+    /// Lays the table out in aligned columns, with each number rounded to 3 decimal places
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let headers = self.column_headers();
+        let row_width = self.rows.values.clone()
+            .map(|value| value.to_string().len())
+            .chain(std::iter::once(self.rows.name.to_string().len()))
+            .max()
+            .unwrap_or(0);
+        let widths: Vec<usize> = headers.iter().map(|header| header.len().max(5)).collect();
+
+        f.write_fmt(format_args!("{:<row_width$}", self.rows.name))?;
+        for (header, width) in headers.iter().zip(widths.iter()) {
+            f.write_fmt(format_args!(" | {:>width$}", header))? }
+        for (row, cells) in self.iter() {
+            f.write_fmt(format_args!("\n{:<row_width$}", row))?;
+            for (cell, width) in cells.iter().zip(widths.iter()) {
+                f.write_fmt(format_args!(" | {:>width$.3}", cell))? } }
+        Ok(()) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZero, rc::Rc};
+    use crate::{
+        Name,
+        premade::number,
+        random::with_seed,
+        rollers::{Sweep, SweepAxis, SubRoller} };
+
+    #[test]
+    fn pool_size_by_target() {
+        let (unit, dice) = number::build();
+        let d6 = dice[3].clone();
+        let sut = Sweep::new(SweepAxis::new(Name::new("Dice").unwrap(), 1..=2), NonZero::new(20_000).unwrap())
+            .by(SweepAxis::new(Name::new("Target").unwrap(), 6..=7))
+            .table_with(
+                with_seed(4),
+                |pool, _| -> Rc<dyn SubRoller> { d6.clone().n_times(pool as u8) },
+                |stats, _, target| stats.probability_at_least(&unit, target));
+
+        let expected = [(1, 6, 6.0 / 36.0), (1, 7, 0.0), (2, 6, 26.0 / 36.0), (2, 7, 21.0 / 36.0)];
+        for (pool, target, chance) in expected {
+            assert!((sut.get(pool, target).unwrap() - chance).abs() < 0.02); }
+        assert!(sut.get(3, 6).is_none());
+        assert!(sut.to_csv().starts_with("Dice,Target 6,Target 7\n1,"));
+        assert!(sut.to_string().starts_with("Dice | Target 6 | Target 7\n1    |    0.16")); }
+
+    #[test]
+    fn csv_headers_are_escaped() {
+        let (_, dice) = number::build();
+        let sut = Sweep::new(SweepAxis::new(Name::new("Dice, pooled").unwrap(), 1..=1), NonZero::new(10).unwrap())
+            .by(SweepAxis::new(Name::new("\"Target\"").unwrap(), 6..=6))
+            .table_with(
+                with_seed(4),
+                |_, _| -> Rc<dyn SubRoller> { dice[3].clone() },
+                |_, _, _| 0.0);
+        assert!(sut.to_csv().starts_with("\"Dice, pooled\",\"\"\"Target\"\" 6\"\n")); }
+}
@@ -0,0 +1,148 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    rc::Rc };
+use crate::{
+    Unit,
+    rollers::Histogram,
+    units::tiered::{format_label, range_str} };
+
+
+/// How often the total of a tiered `Unit` (such as a `TieredUnit`) landed in each of its tiers.
+/// This is usually more telling than the average for systems like Star Wars, where what matters
+/// is whether the roll came out as Successes or Failures.
+#[derive(Clone, Debug)]
+pub struct TierDistribution {
+    pub unit: Rc<dyn Unit>,
+    shares: Vec<TierShare>,
+}
+impl TierDistribution {
+    /// Sorts the counted totals into the `Unit`'s tiers. Totals that no tier covers go to the
+    /// `Unit`'s fallback, if it has one. Returns None if the `Unit` doesn't have any tiers.
+    pub fn from_histogram(histogram: &Histogram) -> Option<Self> {
+        let tiers = histogram.unit.tiers();
+        if tiers.is_empty() { return None }
+        let mut shares: Vec<TierShare> = tiers.iter().enumerate()
+            .map(|(idx, tier)| TierShare {
+                label: tier.label(),
+                tier: Some(idx),
+                range: Some(tier.range.clone()),
+                proportion: 0.0 })
+            .collect();
+        let mut untiered = 0.0;
+        for (total, _) in histogram.iter() {
+            let proportion = histogram.proportion_of(*total);
+            // the first tier that has it wins, the same as when outputting the total
+            match tiers.iter().position(|tier| tier.contains(*total)) {
+                Some(idx) => shares[idx].proportion += proportion,
+                None => untiered += proportion } }
+        if untiered > 0.0 {
+            let label = histogram.unit.fallback()
+                .and_then(format_label)
+                .unwrap_or_else(|| "No Tier".to_string());
+            shares.push(TierShare { label, tier: None, range: None, proportion: untiered }); }
+        Some(Self { unit: histogram.unit.clone(), shares }) }
+
+    /// Iterates through each tier's share of the runs, in the same order as the `Unit`'s tiers. If
+    /// any totals weren't covered by a tier, they're grouped at the end, with no range; they're
+    /// labelled from the `Unit`'s fallback format if it has one, or "No Tier" if not.
+    pub fn iter(&self) -> impl Iterator<Item=&TierShare> { self.shares.iter() }
+
+    /// Fraction (0 to 1) of the runs that landed in the tier at the given index of the `Unit`'s
+    /// `tiers()`. Use this when more than one tier has the same label.
+    pub fn proportion_of_tier(&self, idx: usize) -> f64 {
+        self.shares.iter()
+            .find(|share| share.tier == Some(idx))
+            .map_or(0.0, |share| share.proportion) }
+
+    /// Fraction (0 to 1) of the runs that landed in a tier with the given label. If more than one
+    /// tier has the label, their shares are added together; see `proportion_of_tier()` to tell
+    /// them apart.
+    pub fn proportion_of(&self, label: &str) -> f64 {
+        self.shares.iter()
+            .filter(|share| share.label == label)
+            .map(|share| share.proportion)
+            .sum() }
+}
+impl Display for TierDistribution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}:\n", self.unit))?;
+        for share in self.iter() {
+            // tiers with the same label get their ranges shown, so they can be told apart
+            let shared_label = self.shares.iter().filter(|other| other.label == share.label).count() > 1;
+            match &share.range {
+                Some(range) if shared_label => f.write_fmt(format_args!(
+                    "  {} ({}): {:.1}%\n", share.label, range_str(range), share.proportion * 100.0))?,
+                _ => f.write_fmt(format_args!("  {}\n", share))? } }
+        Ok(()) }
+}
+
+
+/// The fraction of runs that landed in a single tier
+#[derive(Clone, Debug)]
+pub struct TierShare {
+    pub label: String,
+    /// The tier's index in the `Unit`'s `tiers()`, or None for totals that no tier covers
+    pub tier: Option<usize>,
+    /// The tier's range, or None for totals that no tier covers
+    pub range: Option<RangeInclusive<i32>>,
+    pub proportion: f64,
+}
+impl Display for TierShare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}: {:.1}%", self.label, self.proportion * 100.0)) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit,
+        rollers::{Histogram, TierDistribution},
+        units::{DNumUnit, TieredUnit} };
+
+    #[test]
+    fn buckets_totals_by_tier() {
+        let unit: Rc<dyn Unit> = TieredUnit::pos_neg(Name::new("Advantage").unwrap(), "{} Advantage", "{|} Threat");
+        let histogram = Histogram::from_values(unit, &[-2, -1, 0, 1, 1, 3, 0, 2]);
+        let sut = TierDistribution::from_histogram(&histogram).unwrap();
+
+        assert_eq!(sut.proportion_of("Threat"), 0.25);
+        assert_eq!(sut.proportion_of("Advantage"), 0.5);
+        assert_eq!(sut.proportion_of("No Tier"), 0.25);
+        assert_eq!(sut.to_string(), "Advantage:\n  Threat: 25.0%\n  Advantage: 50.0%\n  No Tier: 25.0%\n"); }
+
+    #[test]
+    fn tiers_with_the_same_label_stay_apart() {
+        let unit: Rc<dyn Unit> = TieredUnit::pos_zero_neg(
+            Name::new("Success").unwrap(), "{} Successes", "{} Successes", "{|} Failures");
+        let histogram = Histogram::from_values(unit, &[-1, 0, 0, 2]);
+        let sut = TierDistribution::from_histogram(&histogram).unwrap();
+
+        assert_eq!(sut.proportion_of_tier(0), 0.25);
+        assert_eq!(sut.proportion_of_tier(1), 0.5);
+        assert_eq!(sut.proportion_of_tier(2), 0.25);
+        assert_eq!(sut.proportion_of("Successes"), 0.75);
+        assert_eq!(sut.to_string(),
+            "Success:\n  Failures: 25.0%\n  Successes (0..=0): 50.0%\n  Successes (1..=MAX): 25.0%\n"); }
+
+    #[test]
+    fn uncovered_totals_use_the_fallback() {
+        let unit: Rc<dyn Unit> = TieredUnit::builder(Name::new("Degree").unwrap())
+            .tier(i32::MIN..=0, "Failure")
+            .tier(5..=i32::MAX, "Success")
+            .fallback("{} Partial")
+            .build().unwrap();
+        let histogram = Histogram::from_values(unit, &[0, 2, 3, 6]);
+        let sut = TierDistribution::from_histogram(&histogram).unwrap();
+
+        assert_eq!(sut.proportion_of("Partial"), 0.5);
+        assert_eq!(sut.proportion_of("No Tier"), 0.0);
+        assert_eq!(sut.to_string(), "Degree:\n  Failure: 25.0%\n  Success: 25.0%\n  Partial: 50.0%\n"); }
+
+    #[test]
+    fn untiered_units_have_no_distribution() {
+        let unit: Rc<dyn Unit> = DNumUnit::new();
+        assert!(TierDistribution::from_histogram(&Histogram::from_values(unit, &[1, 2])).is_none()); }
+}
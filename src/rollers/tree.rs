@@ -0,0 +1,207 @@
+use crate::{
+    rollers::{DieRoll, Roll},
+    serialize::json_string };
+
+
+/// What happened to a die that was rolled, as far as the totals are concerned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DieStatus {
+    /// Counted the normal way
+    Kept,
+    /// Dropped by a pool, so it isn't counted
+    Dropped,
+    /// Came up on a face that explodes, so more dice were rolled because of it. It's still counted.
+    Exploded,
+    /// Rolled because another die exploded. It's counted.
+    FromExplosion,
+    /// Only decided how many dice to roll or how big a die to roll (see `DynamicPoolRoller`), so it
+    /// isn't counted
+    Determining,
+}
+impl DieStatus {
+    /// Whether the die counts towards the totals
+    pub fn counts(&self) -> bool {
+        matches!(self, DieStatus::Kept | DieStatus::Exploded | DieStatus::FromExplosion) }
+
+    /// A short name for the status, such as "from_explosion", used as its key in serialized results
+    pub fn key(&self) -> &'static str {
+        match self {
+            DieStatus::Kept => "kept",
+            DieStatus::Dropped => "dropped",
+            DieStatus::Exploded => "exploded",
+            DieStatus::FromExplosion => "from_explosion",
+            DieStatus::Determining => "determining" } }
+}
+
+
+/// A single die from anywhere in a roll, found by `Roll::all_dice()`
+#[derive(Clone, Debug)]
+pub struct RolledDie<'a> {
+    pub roll: &'a DieRoll,
+    pub status: DieStatus,
+    /// The status the die's own part gave it, before anything around it (such as a pool dropping
+    /// it) took over. An exploding die in a dropped roll is `Dropped`, but its own status is still
+    /// `Exploded`, so it can still be shown as part of its explosion.
+    pub own_status: DieStatus,
+    /// Whether the die was rolled because of an explosion, even if it then exploded itself (which
+    /// makes its `own_status` `Exploded` rather than `FromExplosion`)
+    pub from_explosion: bool,
+    /// Where the die is in the roll, as the index of each part on the way down to it. For example,
+    /// in "4d6 drop lowest + d8", the d8 is at [1], and the second kept d6 is at [0, 1]. Pools list
+    /// their kept rolls before their dropped ones, and explosions put the die that exploded first.
+    pub path: Vec<usize>,
+}
+impl RolledDie<'_> {
+    /// Writes the die as a JSON object, with what was rolled, how it counted, and how to draw it.
+    /// Presentation details that the die or face don't have are null, except for the shape and
+    /// labels, which fall back on the usual ones (see `Die::shape()` and `Face::short_label()`).
+    pub fn to_json(&self) -> String {
+        let die = self.roll.die();
+        let face = self.roll.face();
+        let optional = |text: &Option<String>| text.as_deref().map_or("null".to_string(), json_string);
+        let path = self.path.iter()
+            .map(usize::to_string)
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            "{{\"die\":{},\"face\":{},\"short_label\":{},\"long_label\":{},\"status\":{},\"path\":[{}],\"colour\":{},\"shape\":{},\"glyph\":{},\"icon\":{}}}",
+            json_string(&die.name),
+            json_string(&face.label),
+            json_string(face.short_label()),
+            json_string(face.long_label()),
+            json_string(self.status.key()),
+            path,
+            optional(&die.presentation.colour.map(|colour| colour.to_string())),
+            json_string(&die.shape().key()),
+            optional(&face.presentation.glyph),
+            optional(&face.presentation.icon)) }
+}
+
+
+/// `RollWalker` goes through every part of a roll, collecting each die along with its status and
+/// path. `Roll`s tell it about their parts in `walk()`, by passing it each part along with its
+/// index and how that part is treated, and they tell it about the dice themselves with `die()`.
+///
+/// A status that keeps a part from counting (such as `Dropped`) is passed down to all the dice in
+/// it, even if something inside the part would otherwise give them a different status. The status
+/// they would have had is kept as their `own_status`.
+pub struct RollWalker<'a> {
+    path: Vec<usize>,
+    status: DieStatus,
+    own_status: DieStatus,
+    from_explosion: bool,
+    dice: Vec<RolledDie<'a>>,
+}
+impl<'a> RollWalker<'a> {
+    pub fn new() -> Self {
+        Self {
+            path: Vec::new(),
+            status: DieStatus::Kept,
+            own_status: DieStatus::Kept,
+            from_explosion: false,
+            dice: Vec::new() } }
+
+    /// Walks through one part of the roll
+    pub fn part(&mut self, idx: usize, status: DieStatus, roll: &'a dyn Roll) {
+        let (outer_status, outer_own_status, outer_from_explosion) = (self.status, self.own_status, self.from_explosion);
+        if outer_status.counts() {
+            self.status = status; }
+        self.own_status = status;
+        self.from_explosion |= status == DieStatus::FromExplosion;
+        self.path.push(idx);
+        roll.walk(self);
+        self.path.pop();
+        (self.status, self.own_status, self.from_explosion) = (outer_status, outer_own_status, outer_from_explosion); }
+
+    /// Records a die at the current path and status
+    pub fn die(&mut self, roll: &'a DieRoll) {
+        self.dice.push(RolledDie {
+            roll,
+            status: self.status,
+            own_status: self.own_status,
+            from_explosion: self.from_explosion,
+            path: self.path.clone() }); }
+
+    /// All the dice walked through so far, in the order they were found
+    pub fn into_dice(self) -> Vec<RolledDie<'a>> { self.dice }
+}
+impl Default for RollWalker<'_> {
+    fn default() -> Self { Self::new() }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit, Value,
+        dice::{Die, Face},
+        premade::number,
+        presentation::Colour,
+        random::with_seed,
+        rollers::{DieStatus, MultiRoller, Roller, Strategy, SubRoller},
+        units::BasicUnit };
+
+    #[test]
+    fn finds_dropped_dice() {
+        let (unit, dice) = number::build();
+        let roller = dice[3].clone()
+            .n_times_and(4, Strategy::DropLowest { count: 1, order_by: vec![unit.clone()] })
+            .unwrap()
+            .plus(dice[4].clone());
+        let roll = roller.inner_roll_with(with_seed(2));
+        let all = roll.all_dice();
+
+        assert_eq!(all.len(), 5);
+        assert_eq!(all.iter().filter(|die| die.status == DieStatus::Dropped).count(), 1);
+        assert_eq!(all[4].path, vec![1]);
+        assert_eq!(all[3].path, vec![0, 3]);
+        let kept: usize = all.iter().filter(|die| die.status.counts()).count();
+        assert_eq!(kept, roll.rolled_faces().len()); }
+
+    #[test]
+    fn finds_exploded_dice() {
+        let (unit, _) = number::build();
+        let explodes: Rc<dyn Unit> = BasicUnit::new(Name::new("Explodes").unwrap(), "", true);
+        let mut faces: Vec<Rc<Face>> = (1..=5)
+            .map(|num| Face::with_one_val(Name::from_num(num), Value::new(&unit, num as i32)))
+            .collect();
+        faces.push(Face::with_two_vals(Name::from_num(6), Value::new(&unit, 6), Value::new(&explodes, 1)));
+        let d6 = Rc::new(Rc::unwrap_or_clone(Die::new(Name::new("d6").unwrap(), faces)).exploding_on(explodes));
+
+        let roll = (0..)
+            .map(|seed| d6.clone().inner_roll_with(with_seed(seed)))
+            .find(|roll| roll.rolled_faces().len() > 1)
+            .unwrap();
+        let all = roll.all_dice();
+        assert_eq!((all[0].status, all[0].path.clone()), (DieStatus::Exploded, vec![0]));
+        assert!(all[1..].iter().all(|die| die.status.counts() && die.path[0] >= 1));
+        assert_eq!(all.len(), roll.rolled_faces().len()); }
+
+    #[test]
+    fn finds_dice_in_finished_rolls() {
+        let (_, dice) = number::build();
+        let finished = dice[3].clone().n_times(2).roll_with(with_seed(4));
+        assert_eq!(finished.all_dice().len(), 2);
+
+        let multi = MultiRoller::new_numbered([dice[7].clone() as Rc<dyn Roller>, dice[3].clone().n_times(2)]);
+        let paths: Vec<Vec<usize>> = multi.roll_with(with_seed(4)).all_dice().into_iter()
+            .map(|die| die.path)
+            .collect();
+        assert_eq!(paths, vec![vec![0], vec![1, 0], vec![1, 1]]); }
+
+    #[test]
+    fn dice_to_json_has_the_presentation() {
+        let (_, dice) = number::build();
+        let faces = dice[2].faces.iter()
+            .map(|face| face.clone().with_glyph(&face.label).with_labels(&face.label, &format!("Face {}", face.label)))
+            .collect();
+        let d4 = Rc::new(Die { faces, ..Rc::unwrap_or_clone(dice[2].clone()) })
+            .coloured(Colour::rgb(255, 0, 0));
+        let roll = d4.plus(dice[0].clone()).inner_roll_with(with_seed(1));
+        let json = roll.dice_to_json();
+        assert!(json.starts_with(concat!(
+            "[{\"die\":\"d4\",\"face\":\"3\",\"short_label\":\"3\",\"long_label\":\"Face 3\",",
+            "\"status\":\"kept\",\"path\":[0],\"colour\":\"#ff0000\",\"shape\":\"d4\",\"glyph\":\"3\",\"icon\":null}")));
+        assert!(json.contains("\"shape\":\"coin\"")); }
+}
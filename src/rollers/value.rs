@@ -4,7 +4,7 @@ use std::{
 use crate::{
     Name, Values,
     random::Rng,
-    rollers::{DieRoll, Roller, SubRoller, Roll, SubRoll} };
+    rollers::{DieRoll, Roller, SubRoller, Roll, SubRoll, finish::FinishedRoll} };
 
 /// `ValueRoller` is a `Roller` that produces a constant value. It isn't really a "`Roller`" in the
 /// sense that it "rolls" something to come up with a value. Instead, it is used for modifiers to
@@ -31,8 +31,8 @@ impl Roller for ModifierRoller {
     fn description(&self) -> String {
         self.name.clone().map_or(self.values.to_string(), |name| name.deref().to_owned()) }
 
-    fn roll_with(self: Rc<Self>, _rng: Rng) -> Box<dyn Roll> {
-        ValueRoll::new(self.name.clone(), self.values.clone()) }
+    fn roll_with(self: Rc<Self>, rng: Rng) -> Box<dyn Roll> {
+        FinishedRoll::new(self.inner_roll_with(rng)) }
 }
 impl SubRoller for ModifierRoller {
     /// `true` if `name` is `Some(Name)`, else `false`
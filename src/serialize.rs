@@ -0,0 +1,26 @@
+//! Helpers for writing values out as CSV and JSON by hand, shared by the stats exports and the
+//! roll trees.
+
+
+/// Quotes the field if it has anything in it that would break up the CSV
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")) }
+    else {
+        field.to_owned() } }
+
+
+/// Quotes the text as a JSON string, escaping anything that needs it
+pub(crate) fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c) } }
+    out.push('"');
+    out }
@@ -0,0 +1,229 @@
+use std::f32::consts::PI;
+use crate::{
+    presentation::{Colour, DieShape},
+    rollers::{DieStatus, Roll, RolledDie} };
+
+
+/// `SvgRenderer` draws the dice of a roll as an SVG image, such as for posting in chat or printing
+/// on a handout. Each die is drawn in a row in the order `Roll::all_dice()` finds them, using
+/// its shape and colour from its `DiePresentation`, with its face's glyph (or short label) written
+/// on it. Dice that were dropped are greyed out, and dice rolled because of an
+/// explosion are chained to the die that exploded, even if the whole explosion was dropped.
+///
+/// The image is plain SVG that doesn't load anything, not even fonts; the text uses whatever
+/// sans-serif font the viewer has.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgRenderer {
+    /// How wide and tall each die is
+    pub die_size: u32,
+    /// The space between dice, which is also where the chains of explosions are drawn
+    pub gap: u32,
+    /// The colour of dice that don't have one of their own
+    pub default_colour: Colour,
+    /// The colour of dice that were dropped, no matter what colour they are
+    pub greyed_colour: Colour,
+    /// The colour of the outlines and chains
+    pub outline_colour: Colour,
+}
+impl SvgRenderer {
+    pub fn new() -> Self {
+        Self {
+            die_size: 64,
+            gap: 16,
+            default_colour: Colour::rgb(0xf0, 0xf0, 0xf0),
+            greyed_colour: Colour::rgb(0xc8, 0xc8, 0xc8),
+            outline_colour: Colour::rgb(0x33, 0x33, 0x33) } }
+
+    /// Draws every die rolled in the roll, such as the one `Roller::roll()` returns
+    pub fn render(&self, roll: &dyn Roll) -> String {
+        self.render_dice(&roll.all_dice()) }
+
+    /// Draws the given dice, such as the ones from `Roll::all_dice()`, filtered down to only some
+    /// of them
+    pub fn render_dice(&self, dice: &[RolledDie]) -> String {
+        let size = self.die_size as f32;
+        let step = size + self.gap as f32;
+        let width = if dice.is_empty() { 0.0 } else { step * dice.len() as f32 - self.gap as f32 };
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+            num(width),
+            num(size));
+        for (idx, die) in dice.iter().enumerate() {
+            let left = step * idx as f32;
+            if idx > 0 && is_chained(&dice[idx - 1], die) {
+                out += &self.chain(left - self.gap as f32, left, size / 2.0); }
+            out += &self.die(die, left); }
+        out += "</svg>";
+        out }
+
+    /// Draws a single die with its left edge at `left`
+    fn die(&self, die: &RolledDie, left: f32) -> String {
+        let size = self.die_size as f32;
+        let greyed = die.status == DieStatus::Dropped;
+        let colour = if greyed { self.greyed_colour }
+            else { die.roll.die().presentation.colour.unwrap_or(self.default_colour) };
+        let text_colour = if greyed { Colour::rgb(0x80, 0x80, 0x80) }
+            else if colour.brightness() > 0.5 { Colour::rgb(0, 0, 0) }
+            else { Colour::rgb(0xff, 0xff, 0xff) };
+        let face = die.roll.face();
+        let text = face.presentation.glyph.as_deref().unwrap_or(face.short_label());
+        let font_size = size * 0.4 * (2.0 / text.chars().count().max(2) as f32).max(0.35);
+        let dash = if greyed { " stroke-dasharray=\"4 3\"" } else { "" };
+        format!(
+            "<g><title>{}: {} ({})</title>{}<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text></g>",
+            escape(&die.roll.die().name),
+            escape(face.long_label()),
+            die.status.key().replace('_', " "),
+            self.shape(die.roll.die().shape(), left, &format!("fill=\"{}\" stroke=\"{}\" stroke-width=\"2\"{}", colour, self.outline_colour, dash)),
+            num(left + size / 2.0),
+            num(size / 2.0),
+            num(font_size),
+            text_colour,
+            escape(text)) }
+
+    /// Draws the outline of a die's shape, filling in the cell with its left edge at `left`
+    fn shape(&self, shape: DieShape, left: f32, style: &str) -> String {
+        let size = self.die_size as f32;
+        let (cx, cy, radius) = (left + size / 2.0, size / 2.0, size / 2.0 - 2.0);
+        let polygon = |corners: &[(f32, f32)]| {
+            let points = corners.iter()
+                .map(|(x, y)| format!("{},{}", num(cx + x * radius), num(cy + y * radius)))
+                .collect::<Vec<String>>()
+                .join(" ");
+            format!("<polygon points=\"{}\" {}/>", points, style) };
+        match shape {
+            DieShape::Coin => format!(
+                "<circle cx=\"{0}\" cy=\"{1}\" r=\"{2}\" {4}/><circle cx=\"{0}\" cy=\"{1}\" r=\"{3}\" fill=\"none\" stroke=\"{5}\"/>",
+                num(cx), num(cy), num(radius), num(radius * 0.8), style, self.outline_colour),
+            DieShape::D4 => polygon(&regular(3)),
+            DieShape::D6 => format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" rx=\"{3}\" {4}/>",
+                num(cx - radius * 0.85), num(cy - radius * 0.85), num(radius * 1.7), num(radius * 0.2), style),
+            DieShape::D8 => polygon(&regular(4)),
+            DieShape::D10 => polygon(&[(0.0, -1.0), (0.9, -0.15), (0.0, 1.0), (-0.9, -0.15)]),
+            DieShape::D12 => polygon(&regular(5)),
+            DieShape::D20 => polygon(&regular(6)),
+            DieShape::Other(_) => format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+                num(cx), num(cy), num(radius), style) } }
+
+    /// Draws the link between a die and the one rolled because it exploded, as an arrow across the gap
+    fn chain(&self, from: f32, to: f32, y: f32) -> String {
+        let head = (to - from).min(6.0);
+        format!(
+            "<line x1=\"{}\" y1=\"{3}\" x2=\"{}\" y2=\"{3}\" stroke=\"{5}\" stroke-width=\"2\"/><polygon points=\"{2},{3} {1},{4} {1},{6}\" fill=\"{5}\"/>",
+            num(from), num(to - head), num(to), num(y), num(y - head / 2.0), self.outline_colour, num(y + head / 2.0)) }
+}
+impl Default for SvgRenderer {
+    fn default() -> Self { Self::new() }
+}
+
+
+/// Whether `next` was rolled because of an explosion that `prev` is part of. `next` is either
+/// rolled because of an explosion, or it exploded itself while being rolled because of `prev`'s
+/// explosion, in which case it's inside the same part as `prev`. It goes by their `own_status`, so
+/// dropping the explosion doesn't break the chain.
+fn is_chained(prev: &RolledDie, next: &RolledDie) -> bool {
+    match next.own_status {
+        DieStatus::FromExplosion => true,
+        DieStatus::Exploded => matches!(prev.own_status, DieStatus::Exploded | DieStatus::FromExplosion)
+            && next.path.starts_with(&prev.path[..prev.path.len().saturating_sub(1)]),
+        _ => false } }
+
+
+/// The corners of a regular polygon with a corner at the top, as offsets from its centre out to a
+/// radius of 1
+fn regular(sides: usize) -> Vec<(f32, f32)> {
+    (0..sides)
+        .map(|idx| {
+            let angle = 2.0 * PI * idx as f32 / sides as f32 - PI / 2.0;
+            (angle.cos(), angle.sin()) })
+        .collect() }
+
+
+/// Writes the number with at most one decimal place, leaving off ".0"
+fn num(num: f32) -> String {
+    let out = format!("{:.1}", num);
+    out.strip_suffix(".0").map_or(out.clone(), str::to_string) }
+
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;") }
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::{
+        Name, Unit, Value,
+        dice::{Die, Face},
+        premade::{number, star_wars},
+        random::with_seed,
+        rollers::{DieStatus, Roller, Strategy, SubRoller},
+        svg::SvgRenderer,
+        units::BasicUnit };
+
+    #[test]
+    fn draws_each_die() {
+        let (unit, dice) = number::build();
+        let roll = dice[3].clone()
+            .n_times_and(3, Strategy::DropLowest { count: 1, order_by: vec![unit.clone()] })
+            .unwrap()
+            .plus(dice[7].clone())
+            .roll_with(with_seed(3));
+        let svg = SvgRenderer::new().render(roll.as_ref());
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"304\" height=\"64\""));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<g>").count(), 4);
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+        assert!(svg.contains("(dropped)</title>"));
+        assert!(!svg.contains("<line"));
+
+        let (_, star_wars) = star_wars::build();
+        let ability = SvgRenderer::new().render(star_wars[0].clone().inner_roll_with(with_seed(3)).as_ref());
+        assert!(ability.contains("fill=\"#3a9a3a\"")); }
+
+    fn exploding_coin(unit: &Rc<dyn Unit>) -> Rc<Die> {
+        let explodes: Rc<dyn Unit> = BasicUnit::new(Name::new("Explodes").unwrap(), "", true);
+        let faces = vec![
+            Face::with_one_val(Name::from_num(1), Value::new(unit, 1)),
+            Face::with_two_vals(Name::from_num(2), Value::new(unit, 2), Value::new(&explodes, 1))];
+        Rc::new(Rc::unwrap_or_clone(Die::new(Name::new("Coin").unwrap(), faces)).exploding_on(explodes)) }
+
+    #[test]
+    fn chains_explosions() {
+        let (unit, _) = number::build();
+        let coin = exploding_coin(&unit);
+
+        let roll = (0..)
+            .map(|seed| coin.clone().inner_roll_with(with_seed(seed)))
+            .find(|roll| roll.all_dice().len() > 2)
+            .unwrap();
+        let svg = SvgRenderer::new().render(roll.as_ref());
+        assert_eq!(svg.matches("<line").count(), roll.all_dice().len() - 1);
+        assert_eq!(svg.matches("r=\"30\"").count(), roll.all_dice().len()); }
+
+    #[test]
+    fn chains_dropped_explosions() {
+        let (unit, _) = number::build();
+        let pool = exploding_coin(&unit)
+            .n_times_and(2, Strategy::DropHighest { count: 1, order_by: vec![unit.clone()] })
+            .unwrap();
+
+        // the exploded coin beats the one that didn't explode, so it's the one dropped
+        let roll = (0..)
+            .map(|seed| pool.clone().roll_with(with_seed(seed)))
+            .find(|roll| roll.all_dice().iter().any(|die| die.status == DieStatus::Dropped && die.own_status == DieStatus::Exploded))
+            .unwrap();
+        let dropped = roll.all_dice().iter().filter(|die| die.status == DieStatus::Dropped).count();
+        assert!(dropped >= 2);
+        let svg = SvgRenderer::new().render(roll.as_ref());
+        assert_eq!(svg.matches("<line").count(), dropped - 1);
+        assert_eq!(svg.matches("stroke-dasharray").count(), dropped); }
+}
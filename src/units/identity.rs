@@ -0,0 +1,153 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    sync::{Mutex, atomic::{AtomicU64, Ordering}} };
+use crate::Name;
+
+
+/// Every id handed out by `unique_id()` has this bit set, and no id made by `named_id()` does, so
+/// the two kinds can never collide with each other.
+const UNIQUE_BIT: u64 = 1 << 63;
+
+static NEXT_UNIQUE: AtomicU64 = AtomicU64::new(1);
+
+/// Every named id that's been handed out or claimed, along with who has it, so that two different
+/// `Unit`s can't end up with the same id. Entries are never removed, since a `Unit` with the id
+/// could still be around, so it lasts for the whole run of the program. It only grows by one small
+/// entry for each distinct namespace and name, though, no matter how many `Unit`s are made with them.
+static REGISTRY: Mutex<BTreeMap<u64, Claim>> = Mutex::new(BTreeMap::new());
+
+
+/// Who an id in the `REGISTRY` belongs to
+struct Claim {
+    namespace: String,
+    name: String,
+}
+impl Display for Claim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/{}", self.namespace, self.name)) }
+}
+
+
+/// Creates an id that no other `Unit` in this run of the program has. It comes from a counter, so
+/// it's the same from run to run only if the `Unit`s are created in exactly the same order. For ids
+/// that can be saved and matched up later, use `named_id()` instead.
+pub fn unique_id() -> u64 {
+    UNIQUE_BIT | NEXT_UNIQUE.fetch_add(1, Ordering::Relaxed) }
+
+
+/// Creates an id from a namespace (such as the name of the dice set) and the `Unit`'s name. The same
+/// namespace and name always give the same id, in every run and on every machine, so the id can be
+/// saved and used to match the `Unit` up again later. That also means that `Unit`s made with the
+/// same namespace and name are treated as the same `Unit`.
+///
+/// The id is a hash, so in the astronomically unlikely case that it matches one already given to a
+/// different namespace and name, an `IdCollision` is returned instead of a duplicate id.
+pub fn named_id(namespace: &str, name: &Name) -> Result<u64, IdCollision> {
+    let requested = Claim { namespace: namespace.to_string(), name: name.to_string() };
+    let id = hashed_id(namespace, name);
+    let mut registry = REGISTRY.lock().unwrap();
+    match registry.get(&id) {
+        Some(existing) if existing.name == requested.name && existing.namespace == requested.namespace =>
+            Ok(id),
+        Some(existing) =>
+            Err(IdCollision { id, existing: existing.to_string(), requested: requested.to_string() }),
+        None => {
+            registry.insert(id, requested);
+            Ok(id) } } }
+
+
+/// Checks that an id saved from an earlier run can be given back to the `Unit` with the given
+/// namespace and name, such as when rebuilding it from storage. Only the id that `named_id()` makes
+/// from that namespace and name can be reused, since anything else (a `unique_id()`, 0 for
+/// `DNumUnit`, or another `Unit`'s named id) could already belong to a different `Unit` in this run.
+/// For those, an `IdCollision` is returned.
+pub fn claim_id(id: u64, namespace: &str, name: &Name) -> Result<u64, IdCollision> {
+    if id == hashed_id(namespace, name) { return named_id(namespace, name) }
+    let existing = match id {
+        0 => "DNumUnit".to_string(),
+        id if id & UNIQUE_BIT != 0 => "unique_id()".to_string(),
+        id => REGISTRY.lock().unwrap().get(&id)
+            .map_or_else(|| "a different namespace or name".to_string(), Claim::to_string) };
+    let requested = Claim { namespace: namespace.to_string(), name: name.to_string() };
+    Err(IdCollision { id, existing, requested: requested.to_string() }) }
+
+
+/// The id for a namespace and name, without checking it against the `REGISTRY`
+fn hashed_id(namespace: &str, name: &Name) -> u64 {
+    // 0 belongs to `DNumUnit`
+    match fnv_hash(namespace, name) & !UNIQUE_BIT {
+        0 => 1,
+        id => id } }
+
+
+/// 64-bit FNV-1a, which (unlike the standard library's hasher) is guaranteed to give the same hash
+/// forever. The namespace and name are separated by a byte that can't appear in UTF-8, so
+/// "ab" + "c" and "a" + "bc" hash differently.
+fn fnv_hash(namespace: &str, name: &Name) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    namespace.bytes()
+        .chain(std::iter::once(0xFF))
+        .chain(name.bytes())
+        .fold(OFFSET, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME)) }
+
+
+/// Error for when two different namespace and name combinations hash to the same id, or an id
+/// passed to `claim_id()` can't be reused
+#[derive(Debug)]
+pub struct IdCollision {
+    pub id: u64,
+    /// The "namespace/name" that already has the id, or what the id is reserved for
+    pub existing: String,
+    /// The "namespace/name" that was asking for an id
+    pub requested: String,
+}
+impl Display for IdCollision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "id {} for \"{}\" is already used by \"{}\"", self.id, self.requested, self.existing)) }
+}
+impl Error for IdCollision {
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Name, units::identity::{claim_id, hashed_id, named_id, unique_id}};
+
+    fn name(name: &str) -> Name { Name::new(name).unwrap() }
+
+    #[test]
+    fn named_ids_are_stable() {
+        let first = named_id("star_wars", &name("Success")).unwrap();
+        assert_eq!(named_id("star_wars", &name("Success")).unwrap(), first);
+        assert_ne!(named_id("warhammer", &name("Success")).unwrap(), first);
+        // must never change, or saved ids would stop matching
+        assert_eq!(first, 0x18e5_1392_fe7c_73e5); }
+
+    #[test]
+    fn unique_ids_never_match_named_ones() {
+        let (first, second) = (unique_id(), unique_id());
+        assert_ne!(first, second);
+        assert!(first >> 63 == 1 && named_id("test", &name("Any")).unwrap() >> 63 == 0); }
+
+    #[test]
+    fn claimed_ids_are_checked() {
+        let saved = named_id("claims", &name("Saved")).unwrap();
+        assert_eq!(claim_id(saved, "claims", &name("Saved")).unwrap(), saved);
+        assert!(claim_id(saved, "claims", &name("Other")).is_err());
+        assert!(claim_id(saved, "other claims", &name("Saved")).is_err());
+        assert!(claim_id(unique_id(), "claims", &name("Saved")).is_err());
+        assert!(claim_id(0, "claims", &name("Saved")).is_err());
+
+        // only the id that the namespace and name hash to can be claimed
+        assert!(claim_id(0x1234_5678, "claims", &name("Rebuilt")).is_err());
+
+        // claiming first still lets the namespace ask for it later
+        let rebuilt = hashed_id("claims", &name("Rebuilt"));
+        assert_eq!(claim_id(rebuilt, "claims", &name("Rebuilt")).unwrap(), rebuilt);
+        assert_eq!(named_id("claims", &name("Rebuilt")).unwrap(), rebuilt); }
+}
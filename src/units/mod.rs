@@ -2,11 +2,13 @@ use std::{
     fmt::{Debug, Display, Error, Formatter},
     rc::Rc };
 use crate::{
-    Name, Unit,
+    Aggregation, Bounds, Name, Unit,
     random::new_id };
 
+pub mod identity;
 pub mod tiered;
-pub use tiered::{TieredUnit, Tier};
+pub use identity::{IdCollision, claim_id, named_id, unique_id};
+pub use tiered::{TieredUnit, TieredUnitBuilder, Tier, TierIssue, TierError};
 
 
 /// The `Unit` intended for your typical numeric dice. It doesn't provide any kind of label with the
@@ -35,12 +37,18 @@ impl Debug for DNumUnit {
 /// The format will replace a "{}" with the total or a "{|}" with the absolute value of the total.
 /// If you don't want there to be any output for the `Unit` if the total is zero, you can set
 /// `ignore_zero` to `true`.
-#[derive(Debug)]
+///
+/// `new()` gives it an id that's only good for the current run of the program. If it needs to be
+/// matched up with saved data, put it `in_namespace()` to give it an id based on its name.
+#[derive(Clone, Debug)]
 pub struct BasicUnit {
     id: u64,
     name: Name,
     output_format: String,
     ignore_zero: bool,
+    aggregation: Aggregation,
+    bounds: Bounds,
+    default_value: i32,
 }
 impl BasicUnit {
     pub fn new(name: Name, output_format: &str, ignore_zero: bool) -> Rc<Self> { 
@@ -48,17 +56,61 @@ impl BasicUnit {
             id: new_id(), 
             name, 
             output_format: output_format.into(), 
-            ignore_zero}) }
+            ignore_zero,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 }) }
     
-    pub fn rebuild(id: u64, name: Name, output_format: String, ignore_zero: bool) -> Rc<Self> { 
-        Rc::new(Self { 
-            id, 
+    /// Recreates a saved `BasicUnit` with the id it had before. The id has to be the one `named_id()`
+    /// makes from the namespace and its name; see `claim_id()`.
+    pub fn rebuild(id: u64, namespace: &str, name: Name, output_format: String, ignore_zero: bool) -> Result<Rc<Self>, IdCollision> { 
+        Ok(Rc::new(Self { 
+            id: claim_id(id, namespace, &name)?, 
             name,
             output_format, 
-            ignore_zero}) }
+            ignore_zero,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 })) }
+
+    /// Replaces its id with one made from the namespace and its name, which stays the same across
+    /// runs. See `named_id()`.
+    pub fn in_namespace(self: Rc<Self>, namespace: &str) -> Result<Rc<Self>, IdCollision> {
+        let id = named_id(namespace, &self.name)?;
+        Ok(Rc::new(Self { id, ..Rc::unwrap_or_clone(self) })) }
+
+    /// Takes it out of the `Rc` so that it can be changed. If something else still shares it, the
+    /// change is made to a copy with a new id instead, since the copy isn't the same `Unit` anymore.
+    fn into_owned(self: Rc<Self>) -> Self {
+        Rc::try_unwrap(self).unwrap_or_else(|shared| Self { id: new_id(), ..(*shared).clone() }) }
+
+    /// Changes how its values are combined when rolls are totalled up. See `Aggregation`.
+    pub fn aggregated_by(self: Rc<Self>, aggregation: Aggregation) -> Rc<Self> {
+        Rc::new(Self { aggregation, ..self.into_owned() }) }
+
+    /// Keeps its final totals from going below `floor`. See `Bounds`.
+    pub fn floored_at(self: Rc<Self>, floor: i32) -> Rc<Self> {
+        let bounds = Bounds { floor: Some(floor), ..self.bounds };
+        Rc::new(Self { bounds, ..self.into_owned() }) }
+
+    /// Keeps its final totals from going above `ceiling`. See `Bounds`.
+    pub fn capped_at(self: Rc<Self>, ceiling: i32) -> Rc<Self> {
+        let bounds = Bounds { ceiling: Some(ceiling), ..self.bounds };
+        Rc::new(Self { bounds, ..self.into_owned() }) }
+
+    /// Changes what its total counts as when a roll doesn't have a `Value` for it. See
+    /// `Unit::default_value()`.
+    pub fn defaulting_to(self: Rc<Self>, default_value: i32) -> Rc<Self> {
+        Rc::new(Self { default_value, ..self.into_owned() }) }
 }
 impl Unit for BasicUnit {
     fn id(&self) -> u64 { self.id }
+
+    fn aggregation(&self) -> Aggregation { self.aggregation }
+
+    fn bounds(&self) -> Bounds { self.bounds }
+
+    fn default_value(&self) -> i32 { self.default_value }
     
     fn output_for(&self, total: i32) -> String {
         if total == 0 && self.ignore_zero { return "".to_string() }
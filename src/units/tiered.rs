@@ -1,10 +1,12 @@
 use std::{
-    fmt::{Display, Error, Formatter},
+    error::Error,
+    fmt::{Display, Formatter},
     ops::RangeInclusive,
     rc::Rc };
 use crate:: {
-    Name, Unit,
-    random::new_id };
+    Aggregation, Bounds, Name, Unit,
+    random::new_id,
+    units::{IdCollision, claim_id, named_id} };
 
 
 /// When the range of values changes how you'd give the output, you need a TieredUnit.
@@ -16,19 +18,38 @@ use crate:: {
 /// no point in formatting out the 1 when it will always say 1.
 /// If you want the absolute value of the number (e.g. "2 failures" instead of "-2 failures"), put
 /// a pipe ("|") inside the brackets.
-#[derive(Debug)]
+///
+/// `new()` takes the tiers as they are. If they overlap, the first matching tier wins, and any
+/// total that isn't covered by a tier outputs an empty String (and is hidden by `Values`' display).
+/// If you want those mistakes caught, build it with `builder()` instead, which checks the tiers and
+/// lets you give an explicit fallback format for totals that no tier covers.
+///
+/// Like `BasicUnit`, its id is only good for the current run unless it's put `in_namespace()`.
+#[derive(Clone, Debug)]
 pub struct TieredUnit {
     id: u64,
     name: Name,
     tiers: Vec<Tier>,
+    fallback: Option<String>,
+    aggregation: Aggregation,
+    bounds: Bounds,
+    default_value: i32,
 }
 impl TieredUnit {
     pub fn new(name: Name, tiers: impl Into<Vec<Tier>>) -> Rc<Self> {
         Rc::new(Self { 
             id: new_id(), 
             name,
-            tiers: tiers.into() }) }
+            tiers: tiers.into(),
+            fallback: None,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 }) }
             
+    /// Starts a `TieredUnitBuilder`, which validates the tiers before creating the `TieredUnit`
+    pub fn builder(name: Name) -> TieredUnitBuilder {
+        TieredUnitBuilder::new(name) }
+
     pub fn pos_zero_neg(name: Name, pos_fmt: &str, zero_fmt: &str, neg_fmt: &str) -> Rc<Self> {
         Rc::new(Self {
             id: new_id(),
@@ -36,7 +57,11 @@ impl TieredUnit {
             tiers: vec![
                 Tier{ range: i32::MIN..=-1, output_format: neg_fmt.into() },
                 Tier{ range: 0..=0, output_format: zero_fmt.into() },
-                Tier{ range: 1..=i32::MAX, output_format: pos_fmt.into() }] }) }
+                Tier{ range: 1..=i32::MAX, output_format: pos_fmt.into() }],
+            fallback: None,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 }) }
                 
     pub fn pos_neg(name: Name, pos_fmt: &str, neg_fmt: &str) -> Rc<Self> {
         Rc::new(Self {
@@ -44,30 +69,140 @@ impl TieredUnit {
             name,
             tiers: vec![
                Tier{ range: i32::MIN..=-1, output_format: neg_fmt.into() },
-               Tier{ range: 1..=i32::MAX, output_format: pos_fmt.into() }] }) }
+               Tier{ range: 1..=i32::MAX, output_format: pos_fmt.into() }],
+            fallback: None,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 }) }
     
-    pub fn rebuild(id: u64, name: Name, tiers: impl Into<Vec<Tier>>) -> Rc<Self> { 
-        Rc::new(Self { 
-            id,
+    /// Recreates a saved `TieredUnit` with the id it had before. The id has to be the one `named_id()`
+    /// makes from the namespace and its name; see `claim_id()`.
+    pub fn rebuild(id: u64, namespace: &str, name: Name, tiers: impl Into<Vec<Tier>>) -> Result<Rc<Self>, IdCollision> { 
+        Ok(Rc::new(Self { 
+            id: claim_id(id, namespace, &name)?,
             name,
-            tiers: tiers.into() }) }
+            tiers: tiers.into(),
+            fallback: None,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 })) }
+
+    /// Replaces its id with one made from the namespace and its name, which stays the same across
+    /// runs. See `named_id()`.
+    pub fn in_namespace(self: Rc<Self>, namespace: &str) -> Result<Rc<Self>, IdCollision> {
+        let id = named_id(namespace, &self.name)?;
+        Ok(Rc::new(Self { id, ..Rc::unwrap_or_clone(self) })) }
+
+    /// Takes it out of the `Rc` so that it can be changed. If something else still shares it, the
+    /// change is made to a copy with a new id instead, since the copy isn't the same `Unit` anymore.
+    fn into_owned(self: Rc<Self>) -> Self {
+        Rc::try_unwrap(self).unwrap_or_else(|shared| Self { id: new_id(), ..(*shared).clone() }) }
+
+    /// Changes how its values are combined when rolls are totalled up. See `Aggregation`.
+    pub fn aggregated_by(self: Rc<Self>, aggregation: Aggregation) -> Rc<Self> {
+        Rc::new(Self { aggregation, ..self.into_owned() }) }
+
+    /// Keeps its final totals from going below `floor`. See `Bounds`.
+    pub fn floored_at(self: Rc<Self>, floor: i32) -> Rc<Self> {
+        let bounds = Bounds { floor: Some(floor), ..self.bounds };
+        Rc::new(Self { bounds, ..self.into_owned() }) }
+
+    /// Keeps its final totals from going above `ceiling`. See `Bounds`.
+    pub fn capped_at(self: Rc<Self>, ceiling: i32) -> Rc<Self> {
+        let bounds = Bounds { ceiling: Some(ceiling), ..self.bounds };
+        Rc::new(Self { bounds, ..self.into_owned() }) }
+
+    /// Changes what its total counts as when a roll doesn't have a `Value` for it. See
+    /// `Unit::default_value()`.
+    pub fn defaulting_to(self: Rc<Self>, default_value: i32) -> Rc<Self> {
+        Rc::new(Self { default_value, ..self.into_owned() }) }
+
+    /// Returns the `Tier` that the given total falls into, if any. If the tiers overlap, this is
+    /// the first one that contains it, which is the one used for output.
+    pub fn tier_for(&self, total: i32) -> Option<&Tier> {
+        self.tiers.iter().find(|tier| tier.contains(total)) }
+
+    /// Lists which `Tier` each of the integers in the given range falls into. `None` means that the
+    /// total isn't covered by any tier, so it'll use the fallback format (if there is one).
+    /// Mostly meant for documenting and testing the tiers.
+    pub fn tier_listing(&self, totals: RangeInclusive<i32>) -> Vec<(i32, Option<&Tier>)> {
+        totals
+            .map(|total| (total, self.tier_for(total)))
+            .collect() }
+
+    /// Returns all of the problems with this unit's tiers. See `TierIssue`.
+    pub fn diagnose(&self) -> Vec<TierIssue> {
+        let mut issues = Tier::diagnose(&self.tiers);
+        if self.fallback.is_some() {
+            issues.retain(|issue| !matches!(issue, TierIssue::Gap(_))) }
+        issues }
 }
 impl Unit for TieredUnit {
     fn id(&self) -> u64 { self.id }
     
+    fn tiers(&self) -> &[Tier] { &self.tiers }
+
+    fn fallback(&self) -> Option<&str> { self.fallback.as_deref() }
+
+    fn aggregation(&self) -> Aggregation { self.aggregation }
+
+    fn bounds(&self) -> Bounds { self.bounds }
+
+    fn default_value(&self) -> i32 { self.default_value }
+
     fn output_for(&self, total: i32) -> String {
-        for tier in &self.tiers {
-            if tier.contains(total) {
-                return tier.output_for(total) } }
-        "".to_string() }
+        match (self.tier_for(total), &self.fallback) {
+            (Some(tier), _) => tier.output_for(total),
+            (None, Some(fallback)) => format_total(fallback, total),
+            (None, None) => "".to_string() } }
 }
 impl Display for TieredUnit {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{}", self.name)) }
 }
 
 
-#[derive(Debug)]
+/// Builds a `TieredUnit`, checking that none of its tiers overlap and that every possible total is
+/// covered by a tier. If some totals intentionally aren't covered (e.g. 0 shouldn't show up in the
+/// output), give it a `fallback()` format; an empty one hides those totals, the same as `new()`
+/// does.
+pub struct TieredUnitBuilder {
+    name: Name,
+    tiers: Vec<Tier>,
+    fallback: Option<String>,
+}
+impl TieredUnitBuilder {
+    fn new(name: Name) -> Self {
+        Self { name, tiers: Vec::new(), fallback: None } }
+
+    /// Adds a tier that uses the given output format for totals within the range
+    pub fn tier(mut self, range: RangeInclusive<i32>, output_format: &str) -> Self {
+        self.tiers.push(Tier{ range, output_format: output_format.into() });
+        self }
+
+    /// Sets the output format for any total that isn't covered by a tier
+    pub fn fallback(mut self, output_format: &str) -> Self {
+        self.fallback = Some(output_format.into());
+        self }
+
+    /// Creates the `TieredUnit` if there aren't any problems with the tiers. Otherwise, returns a
+    /// `TierError` listing all of them.
+    pub fn build(self) -> Result<Rc<TieredUnit>, TierError> {
+        let unit = TieredUnit {
+            id: new_id(),
+            name: self.name,
+            tiers: self.tiers,
+            fallback: self.fallback,
+            aggregation: Aggregation::Sum,
+            bounds: Bounds::default(),
+            default_value: 0 };
+        let issues = unit.diagnose();
+        if issues.is_empty() { Ok(Rc::new(unit)) }
+        else { Err(TierError { issues }) } }
+}
+
+
+#[derive(Clone, Debug)]
 pub struct Tier {
     pub range: RangeInclusive<i32>,
     pub output_format: String
@@ -76,5 +211,181 @@ impl Tier {
     pub fn contains(&self, total: i32) -> bool { self.range.contains(&total) }
     
     pub fn output_for(&self, total: i32) -> String {
-        self.output_format.replace("{|}", &total.abs().to_string()).replace("{}",  &total.to_string()) }
+        format_total(&self.output_format, total) }
+
+    /// A name for the tier as a whole, used when reporting how often each tier came up. It's the
+    /// output format without the total in it, so "{|} Failures" is labelled "Failures". If the
+    /// format is nothing but the total, the range is used instead.
+    pub fn label(&self) -> String {
+        format_label(&self.output_format).unwrap_or_else(|| range_str(&self.range)) }
+
+    /// Checks a list of tiers for empty ranges, overlapping ranges, and totals that none of them
+    /// cover, in that order.
+    pub fn diagnose(tiers: &[Tier]) -> Vec<TierIssue> {
+        let mut issues = Vec::new();
+        for tier in tiers.iter().filter(|tier| tier.range.is_empty()) {
+            issues.push(TierIssue::Empty(tier.range.clone())); }
+
+        let mut ranges: Vec<&RangeInclusive<i32>> = tiers.iter()
+            .map(|tier| &tier.range)
+            .filter(|range| !range.is_empty())
+            .collect();
+        ranges.sort_by_key(|range| (*range.start(), *range.end()));
+
+        for (idx, first) in ranges.iter().enumerate() {
+            for second in ranges[idx + 1..].iter() {
+                // sorted by start, so once one starts past the end of first, the rest do too
+                if second.start() > first.end() { break; }
+                issues.push(TierIssue::Overlap(
+                    (*first).clone(),
+                    (*second).clone(),
+                    *second.start()..=*first.end().min(second.end()))) } }
+
+        // i64 so that stepping past i32::MAX doesn't overflow
+        let mut next_uncovered = i32::MIN as i64;
+        for range in ranges {
+            let (start, end) = (*range.start() as i64, *range.end() as i64);
+            if start > next_uncovered {
+                issues.push(TierIssue::Gap(next_uncovered as i32..=(start - 1) as i32)) }
+            next_uncovered = next_uncovered.max(end + 1); }
+        if next_uncovered <= i32::MAX as i64 {
+            issues.push(TierIssue::Gap(next_uncovered as i32..=i32::MAX)) }
+        issues }
+}
+
+
+fn format_total(output_format: &str, total: i32) -> String {
+    output_format.replace("{|}", &total.abs().to_string()).replace("{}",  &total.to_string()) }
+
+
+/// The output format without the total in it, or None if that leaves nothing
+pub(crate) fn format_label(output_format: &str) -> Option<String> {
+    let label = output_format.replace("{|}", "").replace("{}", "");
+    let label = label.trim();
+    if label.is_empty() { None }
+    else { Some(label.to_string()) } }
+
+pub(crate) fn range_str(range: &RangeInclusive<i32>) -> String {
+    let bound = |num: i32| match num {
+        i32::MIN => "MIN".to_string(),
+        i32::MAX => "MAX".to_string(),
+        _ => num.to_string() };
+    format!("{}..={}", bound(*range.start()), bound(*range.end())) }
+
+
+/// A single problem found when checking the tiers of a `TieredUnit`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TierIssue {
+    /// The tier's range doesn't contain anything (its start is after its end)
+    Empty(RangeInclusive<i32>),
+    /// The first two ranges overlap; the third is the range of totals they share
+    Overlap(RangeInclusive<i32>, RangeInclusive<i32>, RangeInclusive<i32>),
+    /// None of the tiers cover this range of totals
+    Gap(RangeInclusive<i32>),
+}
+impl Display for TierIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TierIssue::Empty(range) =>
+                f.write_fmt(format_args!("tier {} is empty", range_str(range))),
+            TierIssue::Overlap(first, second, shared) =>
+                f.write_fmt(format_args!(
+                    "tiers {} and {} overlap on {}",
+                    range_str(first), range_str(second), range_str(shared))),
+            TierIssue::Gap(range) =>
+                f.write_fmt(format_args!("no tier covers {}", range_str(range))) } }
+}
+
+
+/// Error returned from `TieredUnitBuilder::build()` that holds every `TierIssue` that was found
+#[derive(Debug)]
+pub struct TierError {
+    pub issues: Vec<TierIssue>
+}
+impl Display for TierError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let text = self.issues.iter()
+            .map(TierIssue::to_string)
+            .collect::<Vec<String>>()
+            .join("; ");
+        f.write_str(&text) }
+}
+impl Error for TierError {
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aggregation, Name, Unit, units::tiered::{Tier, TieredUnit, TierIssue}};
+
+    fn name(name: &str) -> Name { Name::new(name).unwrap() }
+
+    #[test]
+    fn changing_a_shared_unit_gives_it_a_new_id() {
+        let unit = TieredUnit::pos_neg(name("Advantage"), "{} Advantage", "{|} Threat");
+        let id = unit.id();
+        let shared = unit.clone();
+        let changed = unit.aggregated_by(Aggregation::Max);
+        assert_ne!(changed.id(), id);
+        assert_eq!(shared.id(), id);
+        assert_eq!(shared.aggregation(), Aggregation::Sum);
+
+        // nothing else has it, so it's still the same unit
+        let kept = changed.id();
+        assert_eq!(changed.floored_at(0).id(), kept); }
+
+    #[test]
+    fn builder_accepts_full_coverage() {
+        let unit = TieredUnit::builder(name("Successes"))
+            .tier(i32::MIN..=0, "Failure")
+            .tier(1..=1, "1 Success")
+            .tier(2..=i32::MAX, "{} Successes")
+            .build()
+            .unwrap();
+        assert_eq!(unit.output_for(-3), "Failure");
+        assert_eq!(unit.output_for(1), "1 Success");
+        assert_eq!(unit.output_for(4), "4 Successes"); }
+
+    #[test]
+    fn builder_reports_overlaps_and_gaps() {
+        let err = TieredUnit::builder(name("Successes"))
+            .tier(i32::MIN..=0, "Failure")
+            .tier(0..=3, "{} Successes")
+            .tier(6..=i32::MAX, "Lots")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.issues, vec![
+            TierIssue::Overlap(i32::MIN..=0, 0..=3, 0..=0),
+            TierIssue::Gap(4..=5)]);
+        assert_eq!(err.to_string(), "tiers MIN..=0 and 0..=3 overlap on 0..=0; no tier covers 4..=5"); }
+
+    #[test]
+    fn fallback_covers_gaps() {
+        let unit = TieredUnit::builder(name("Advantage"))
+            .tier(i32::MIN..=-1, "{|} Threat")
+            .tier(1..=i32::MAX, "{} Advantage")
+            .fallback("")
+            .build()
+            .unwrap();
+        assert_eq!(unit.output_for(0), "");
+        assert_eq!(unit.output_for(-2), "2 Threat"); }
+
+    #[test]
+    fn labels_leave_out_totals() {
+        let unit = TieredUnit::pos_zero_neg(name("Success"), "{} Successes", "{}", "{|} Failures");
+        let labels: Vec<String> = unit.tiers().iter().map(Tier::label).collect();
+        assert_eq!(labels, vec!["Failures", "0..=0", "Successes"]); }
+
+    #[test]
+    fn pos_neg_has_gap_at_zero() {
+        let unit = TieredUnit::pos_neg(name("Advantage"), "{} Advantage", "{|} Threat");
+        assert_eq!(unit.diagnose(), vec![TierIssue::Gap(0..=0)]);
+        let listing: Vec<(i32, Option<String>)> = unit.tier_listing(-1..=1).into_iter()
+            .map(|(total, tier)| (total, tier.map(|tier| tier.output_format.clone())))
+            .collect();
+        assert_eq!(listing, vec![
+            (-1, Some("{|} Threat".to_string())),
+            (0, None),
+            (1, Some("{} Advantage".to_string()))]); }
 }